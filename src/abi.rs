@@ -0,0 +1,174 @@
+//! Solidity ABI types and values.
+
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use web3::types::{
+	H160,
+	U256,
+};
+
+/// Solidity parameter type, used to drive the ABI decoding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParamType {
+	/// `address`.
+	Address,
+	/// `bytes`.
+	Bytes,
+	/// `int<N>`, with the size in bits.
+	Int(usize),
+	/// `uint<N>`, with the size in bits.
+	Uint(usize),
+	/// `bool`.
+	Bool,
+	/// `string`.
+	String,
+	/// `T[]`.
+	Array(Box<ParamType>),
+	/// `bytes<N>`, with the size in bytes.
+	FixedBytes(usize),
+	/// `T[N]`.
+	FixedArray(Box<ParamType>, usize),
+	/// `(T1,T2,...)`.
+	Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+	/// Checks if the type is encoded in the tail, being referenced by an offset in the head.
+	pub fn is_dynamic(&self) -> bool {
+		match self {
+			ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+			ParamType::FixedArray(param, _) => param.is_dynamic(),
+			ParamType::Tuple(params) => params.iter().any(|it| it.is_dynamic()),
+			_ => false,
+		}
+	}
+
+	/// Returns the number of bytes the type takes in the head of the encoding.
+	pub fn head_size(&self) -> usize {
+		match self {
+			ParamType::FixedArray(param, size) if !self.is_dynamic() => param.head_size() * size,
+			ParamType::Tuple(params) if !self.is_dynamic() => params.iter().map(|it| it.head_size()).sum(),
+			_ => 32,
+		}
+	}
+}
+
+/// Solidity value, decoded according to a `ParamType`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Token {
+	/// `address`.
+	Address(H160),
+	/// `bytes`.
+	Bytes(Vec<u8>),
+	/// `int<N>`, in two's complement.
+	Int(U256),
+	/// `uint<N>`.
+	Uint(U256),
+	/// `bool`.
+	Bool(bool),
+	/// `string`.
+	String(String),
+	/// `T[]`.
+	Array(Vec<Token>),
+	/// `bytes<N>`.
+	FixedBytes(Vec<u8>),
+	/// `T[N]`.
+	FixedArray(Vec<Token>),
+	/// `(T1,T2,...)`.
+	Tuple(Vec<Token>),
+}
+
+impl Token {
+	/// Creates an `int<N>` from a signed value, using two's complement.
+	///
+	/// # Arguments
+	///
+	/// * `value` - The signed value.
+	///
+	pub fn int(value: i128) -> Self {
+		if value < 0 {
+			Token::Int(!U256::from((-(value + 1)) as u128))
+		} else {
+			Token::Int(U256::from(value as u128))
+		}
+	}
+
+	/// Checks if the token is encoded in the tail, being referenced by an offset in the head.
+	pub fn is_dynamic(&self) -> bool {
+		match self {
+			Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+			Token::FixedArray(tokens) | Token::Tuple(tokens) => tokens.iter().any(|it| it.is_dynamic()),
+			_ => false,
+		}
+	}
+
+	/// Returns the number of bytes the token takes in the head of the encoding.
+	pub fn head_size(&self) -> usize {
+		match self {
+			Token::FixedArray(tokens) | Token::Tuple(tokens) if !self.is_dynamic() => {
+				tokens.iter().map(|it| it.head_size()).sum()
+			}
+			_ => 32,
+		}
+	}
+
+	/// Returns the address, if it is an `address`.
+	pub fn into_address(self) -> Option<H160> {
+		match self {
+			Token::Address(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Returns the value, if it is an `uint<N>`.
+	pub fn into_uint(self) -> Option<U256> {
+		match self {
+			Token::Uint(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Returns the two's complement value, if it is an `int<N>`.
+	pub fn into_int(self) -> Option<U256> {
+		match self {
+			Token::Int(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Returns the value, if it is a `bool`.
+	pub fn into_bool(self) -> Option<bool> {
+		match self {
+			Token::Bool(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Returns the bytes, if it is a `bytes` or `bytes<N>`.
+	pub fn into_bytes(self) -> Option<Vec<u8>> {
+		match self {
+			Token::Bytes(value) | Token::FixedBytes(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Returns the string, if it is a `string`.
+	pub fn into_string(self) -> Option<String> {
+		match self {
+			Token::String(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Returns the elements, if it is an array or a tuple.
+	pub fn into_tokens(self) -> Option<Vec<Token>> {
+		match self {
+			Token::Array(value) | Token::FixedArray(value) | Token::Tuple(value) => Some(value),
+			_ => None,
+		}
+	}
+}
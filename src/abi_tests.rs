@@ -0,0 +1,65 @@
+use crate::abi::{
+	ParamType,
+	Token,
+};
+use web3::types::{
+	H160,
+	U256,
+};
+
+#[test]
+fn dynamic_types() {
+	assert!(!ParamType::Address.is_dynamic());
+	assert!(!ParamType::Uint(256).is_dynamic());
+	assert!(!ParamType::FixedBytes(10).is_dynamic());
+	assert!(ParamType::Bytes.is_dynamic());
+	assert!(ParamType::String.is_dynamic());
+	assert!(ParamType::Array(Box::new(ParamType::Address)).is_dynamic());
+	assert!(!ParamType::FixedArray(Box::new(ParamType::Address), 2).is_dynamic());
+	assert!(ParamType::FixedArray(Box::new(ParamType::String), 2).is_dynamic());
+	assert!(!ParamType::Tuple(vec![ParamType::Address, ParamType::Bool]).is_dynamic());
+	assert!(ParamType::Tuple(vec![ParamType::Address, ParamType::Bytes]).is_dynamic());
+}
+
+#[test]
+fn head_size() {
+	assert_eq!(32, ParamType::Address.head_size());
+	assert_eq!(32, ParamType::Bytes.head_size());
+	assert_eq!(96, ParamType::FixedArray(Box::new(ParamType::Uint(8)), 3).head_size());
+	assert_eq!(32, ParamType::FixedArray(Box::new(ParamType::String), 3).head_size());
+	assert_eq!(128, ParamType::Tuple(vec![
+		ParamType::Address,
+		ParamType::FixedArray(Box::new(ParamType::Bool), 3),
+	]).head_size());
+}
+
+#[test]
+fn token_accessors() {
+	let address = H160::from_low_u64_be(1);
+	assert_eq!(Some(address), Token::Address(address).into_address());
+	assert_eq!(None, Token::Address(address).into_uint());
+	assert_eq!(Some(U256::from(2)), Token::Uint(2.into()).into_uint());
+	assert_eq!(Some(true), Token::Bool(true).into_bool());
+	assert_eq!(Some(vec![1, 2]), Token::FixedBytes(vec![1, 2]).into_bytes());
+	assert_eq!(Some("one".to_string()), Token::String("one".to_string()).into_string());
+	assert_eq!(Some(vec![Token::Bool(false)]), Token::Tuple(vec![Token::Bool(false)]).into_tokens());
+}
+
+#[test]
+fn signed_integers() {
+	assert_eq!(Token::Int(U256::zero()), Token::int(0));
+	assert_eq!(Token::Int(U256::from(5)), Token::int(5));
+	assert_eq!(Token::Int(U256::MAX), Token::int(-1));
+	assert_eq!(Token::Int(U256::MAX - U256::from(i128::MAX as u128)), Token::int(i128::MIN));
+}
+
+#[test]
+fn dynamic_tokens() {
+	assert!(!Token::Bool(true).is_dynamic());
+	assert!(Token::String("".to_string()).is_dynamic());
+	assert!(Token::Array(vec![]).is_dynamic());
+	assert!(!Token::Tuple(vec![Token::Bool(true), Token::Uint(1.into())]).is_dynamic());
+	assert!(Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![])]).is_dynamic());
+	assert_eq!(64, Token::Tuple(vec![Token::Bool(true), Token::Uint(1.into())]).head_size());
+	assert_eq!(32, Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![])]).head_size());
+}
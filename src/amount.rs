@@ -0,0 +1,230 @@
+//! Token amounts along with their number of decimals.
+
+use crate::ERC20Error;
+use serde::{
+	de,
+	ser,
+	Deserialize,
+	Deserializer,
+	Serialize,
+	Serializer,
+};
+use std::{
+	fmt,
+	str::FromStr,
+};
+use web3::types::U256;
+
+/// The most decimals an amount may have, as `10^78` does not fit in an `U256`.
+const MAX_DECIMALS: u8 = 77;
+
+/// Token amount, the raw on-chain integer along with the number of decimals of the token.
+///
+/// It is formatted and parsed as an exact decimal string, never going through floating point, which
+/// is also its serde representation. The number of decimals is part of the value, so the same
+/// quantity with different decimals is not equal, see `rescale`. It has at most 77 decimals, as
+/// `10^78` does not fit in an `U256`, so it is created with `new` rather than with its fields.
+///
+/// ```
+/// use erc20::amount::TokenAmount;
+/// use std::str::FromStr;
+///
+/// let amount = TokenAmount::new(1_000_000_000.into(), 6).unwrap();
+/// assert_eq!("1000.000000", amount.to_string());
+/// assert_eq!(Ok(amount), TokenAmount::parse("1000", 6));
+///
+/// // Parsing a string keeps all of its decimals.
+/// assert_eq!(TokenAmount::new(15.into(), 1), TokenAmount::from_str("1.5"));
+///
+/// let fee = TokenAmount::parse("0.25", 6).unwrap();
+/// assert_eq!("999.750000", amount.checked_sub(fee).unwrap().to_string());
+/// assert_eq!("999.750000000000000000", amount.checked_sub(fee).unwrap().rescale(18).unwrap().to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenAmount {
+	/// The amount in the smallest unit of the token, as transferred on-chain.
+	pub raw: U256,
+	/// The number of decimals of the token.
+	pub decimals: u8,
+}
+
+impl TokenAmount {
+	/// Creates an amount, failing with `PrecisionLoss` if it has more than 77 decimals.
+	///
+	/// # Arguments
+	///
+	/// * `raw` - The amount in the smallest unit of the token.
+	/// * `decimals` - The number of decimals of the token.
+	///
+	pub fn new(raw: U256, decimals: u8) -> Result<Self, ERC20Error> {
+		if decimals > MAX_DECIMALS {
+			return Err(ERC20Error::PrecisionLoss);
+		}
+		Ok(Self {
+			raw,
+			decimals,
+		})
+	}
+
+	/// Parses a human readable amount, e.g. `1000` or `0.25`, with no sign, exponent, or separators.
+	/// It fails with `PrecisionLoss` if it has more significant decimals than the token, instead of
+	/// rounding it.
+	///
+	/// # Arguments
+	///
+	/// * `value` - The decimal amount.
+	/// * `decimals` - The number of decimals of the token.
+	///
+	pub fn parse(value: &str, decimals: u8) -> Result<Self, ERC20Error> {
+		Self::from_str(value)?.rescale(decimals)
+	}
+
+	/// Returns the integer part of the amount.
+	pub fn integer(&self) -> U256 {
+		match Self::unit(self.decimals) {
+			Some(unit) => self.raw / unit,
+			None => U256::zero(),
+		}
+	}
+
+	/// Checks if the amount is zero.
+	pub fn is_zero(&self) -> bool {
+		self.raw.is_zero()
+	}
+
+	/// Returns the same amount with other decimals. Fails with `PrecisionLoss` if some of the
+	/// decimals would be dropped or there are more than 77, or with `AmountOverflow` if it does not
+	/// fit.
+	///
+	/// # Arguments
+	///
+	/// * `decimals` - The number of decimals.
+	///
+	pub fn rescale(&self, decimals: u8) -> Result<Self, ERC20Error> {
+		if decimals > MAX_DECIMALS {
+			return Err(ERC20Error::PrecisionLoss);
+		}
+		if decimals >= self.decimals {
+			let factor = Self::unit(decimals - self.decimals).ok_or(ERC20Error::AmountOverflow)?;
+			let raw = self.raw.checked_mul(factor).ok_or(ERC20Error::AmountOverflow)?;
+			return Self::new(raw, decimals);
+		}
+		match Self::unit(self.decimals - decimals) {
+			Some(factor) if (self.raw % factor).is_zero() => Self::new(self.raw / factor, decimals),
+			Some(_) => Err(ERC20Error::PrecisionLoss),
+			// The factor exceeds any raw amount, so only zero can be rescaled.
+			None if self.raw.is_zero() => Self::new(U256::zero(), decimals),
+			None => Err(ERC20Error::PrecisionLoss),
+		}
+	}
+
+	/// Adds an amount with the same decimals.
+	///
+	/// # Arguments
+	///
+	/// * `other` - The amount to add.
+	///
+	pub fn checked_add(&self, other: Self) -> Result<Self, ERC20Error> {
+		self.check_decimals(&other)?;
+		let raw = self.raw.checked_add(other.raw).ok_or(ERC20Error::AmountOverflow)?;
+		Self::new(raw, self.decimals)
+	}
+
+	/// Subtracts an amount with the same decimals, failing with `AmountOverflow` if it is greater.
+	///
+	/// # Arguments
+	///
+	/// * `other` - The amount to subtract.
+	///
+	pub fn checked_sub(&self, other: Self) -> Result<Self, ERC20Error> {
+		self.check_decimals(&other)?;
+		let raw = self.raw.checked_sub(other.raw).ok_or(ERC20Error::AmountOverflow)?;
+		Self::new(raw, self.decimals)
+	}
+
+	/// Multiplies the amount by an integer.
+	///
+	/// # Arguments
+	///
+	/// * `factor` - The integer factor.
+	///
+	pub fn checked_mul(&self, factor: U256) -> Result<Self, ERC20Error> {
+		let raw = self.raw.checked_mul(factor).ok_or(ERC20Error::AmountOverflow)?;
+		Self::new(raw, self.decimals)
+	}
+
+	/// Divides the amount by an integer, rounding down to the smallest unit of the token.
+	///
+	/// # Arguments
+	///
+	/// * `divisor` - The integer divisor, failing with `DivisionByZero` if it is zero.
+	///
+	pub fn checked_div(&self, divisor: U256) -> Result<Self, ERC20Error> {
+		let raw = self.raw.checked_div(divisor).ok_or(ERC20Error::DivisionByZero)?;
+		Self::new(raw, self.decimals)
+	}
+
+	fn check_decimals(&self, other: &Self) -> Result<(), ERC20Error> {
+		if self.decimals != other.decimals {
+			return Err(ERC20Error::DecimalsMismatch);
+		}
+		Ok(())
+	}
+
+	/// Returns `10^decimals`, `None` if it overflows.
+	fn unit(decimals: u8) -> Option<U256> {
+		U256::from(10).checked_pow(decimals.into())
+	}
+}
+
+impl fmt::Display for TokenAmount {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let decimals = self.decimals as usize;
+		if decimals == 0 {
+			return write!(f, "{}", self.raw);
+		}
+		let digits = format!("{:0>width$}", self.raw.to_string(), width = decimals + 1);
+		let (integer, fraction) = digits.split_at(digits.len() - decimals);
+		write!(f, "{}.{}", integer, fraction)
+	}
+}
+
+/// Parses the amount with as many decimals as it has digits after the point, the inverse of
+/// `Display`.
+impl FromStr for TokenAmount {
+	type Err = ERC20Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		let (integer, fraction) = match value.find('.') {
+			Some(index) => (&value[..index], &value[index + 1..]),
+			None => (value, ""),
+		};
+		let is_digits = |it: &str| it.chars().all(|digit| digit.is_ascii_digit());
+		let has_point = integer.len() < value.len();
+		if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) || (has_point && fraction.is_empty()) {
+			return Err(ERC20Error::InvalidAmount);
+		}
+		if fraction.len() > MAX_DECIMALS as usize {
+			return Err(ERC20Error::PrecisionLoss);
+		}
+		let raw = U256::from_dec_str(&format!("{}{}", integer, fraction)).map_err(|_| ERC20Error::AmountOverflow)?;
+		Self::new(raw, fraction.len() as u8)
+	}
+}
+
+/// Fails for amounts with more than 77 decimals, which could not be deserialized.
+impl Serialize for TokenAmount {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if self.decimals > MAX_DECIMALS {
+			return Err(ser::Error::custom(format!("too many decimals {}", self.decimals)));
+		}
+		serializer.collect_str(self)
+	}
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let value = String::deserialize(deserializer)?;
+		Self::from_str(&value).map_err(|_| de::Error::custom(format!("invalid token amount {:?}", value)))
+	}
+}
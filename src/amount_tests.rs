@@ -0,0 +1,92 @@
+use crate::{
+	amount::TokenAmount,
+	ERC20Error,
+};
+use std::str::FromStr;
+use web3::types::U256;
+
+fn amount(raw: u64, decimals: u8) -> TokenAmount {
+	TokenAmount::new(raw.into(), decimals).unwrap()
+}
+
+#[test]
+fn format_amounts() {
+	assert_eq!("1000.000000", amount(1_000_000_000, 6).to_string());
+	assert_eq!("0.000001", amount(1, 6).to_string());
+	assert_eq!("0.000000", amount(0, 6).to_string());
+	assert_eq!("12.34567890", amount(1_234_567_890, 8).to_string());
+	assert_eq!("42", amount(42, 0).to_string());
+	assert_eq!(
+		"115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+		TokenAmount::new(U256::max_value(), 18).unwrap().to_string(),
+	);
+	assert_eq!(U256::from(1000), amount(1_000_999_999, 6).integer());
+}
+
+#[test]
+fn parse_amounts() {
+	assert_eq!(Ok(amount(1_000_000_000, 6)), TokenAmount::parse("1000", 6));
+	assert_eq!(Ok(amount(250_000, 6)), TokenAmount::parse("0.25", 6));
+	assert_eq!(Ok(amount(1, 6)), TokenAmount::parse("0.000001", 6));
+	assert_eq!(Ok(amount(1_000_000, 6)), TokenAmount::parse("1.000000000", 6));
+	assert_eq!(Ok(amount(42, 0)), TokenAmount::parse("42", 0));
+	assert_eq!(Err(ERC20Error::PrecisionLoss), TokenAmount::parse("0.0000001", 6));
+	assert_eq!(Err(ERC20Error::PrecisionLoss), TokenAmount::parse("1.5", 0));
+
+	for invalid in &["", ".", "1.", ".5", "-1", "+1", "1e18", "1,000", " 1", "1.2.3", "0x10"] {
+		assert_eq!(Err(ERC20Error::InvalidAmount), TokenAmount::parse(invalid, 18), "{:?}", invalid);
+	}
+	let too_big = format!("{}0", U256::max_value());
+	assert_eq!(Err(ERC20Error::AmountOverflow), TokenAmount::parse(&too_big, 0));
+	assert_eq!(Err(ERC20Error::AmountOverflow), TokenAmount::parse(&U256::max_value().to_string(), 1));
+
+	// The formatted amount is parsed back with the same decimals.
+	for value in &[amount(0, 0), amount(1, 18), amount(1_234_567_890, 8), TokenAmount::new(U256::max_value(), 77).unwrap()] {
+		assert_eq!(Ok(*value), TokenAmount::from_str(&value.to_string()));
+	}
+}
+
+#[test]
+fn rescale_amounts() {
+	assert_eq!(Ok(amount(1_000_000_000_000_000_000, 18)), amount(1_000_000, 6).rescale(18));
+	assert_eq!(Ok(amount(1_000_000, 6)), amount(1_000_000_000_000_000_000, 18).rescale(6));
+	assert_eq!(Err(ERC20Error::PrecisionLoss), amount(1_000_000_000_000_000_001, 18).rescale(6));
+	assert_eq!(Ok(amount(0, 0)), TokenAmount { raw: U256::zero(), decimals: 200 }.rescale(0));
+	assert_eq!(Err(ERC20Error::PrecisionLoss), TokenAmount { raw: U256::one(), decimals: 200 }.rescale(0));
+	assert_eq!(Err(ERC20Error::AmountOverflow), amount(2, 0).rescale(77));
+	assert_eq!(Err(ERC20Error::PrecisionLoss), amount(1, 0).rescale(78));
+	assert_eq!(Err(ERC20Error::PrecisionLoss), amount(0, 0).rescale(78));
+	assert_eq!(Ok(amount(0, 77)), amount(0, 0).rescale(77));
+	assert_eq!(Err(ERC20Error::AmountOverflow), TokenAmount::new(U256::max_value(), 0).unwrap().rescale(1));
+}
+
+#[test]
+fn checked_arithmetic() {
+	let (a, b) = (amount(1_500_000, 6), amount(250_000, 6));
+	assert_eq!(Ok(amount(1_750_000, 6)), a.checked_add(b));
+	assert_eq!(Ok(amount(1_250_000, 6)), a.checked_sub(b));
+	assert_eq!(Err(ERC20Error::AmountOverflow), b.checked_sub(a));
+	assert_eq!(Err(ERC20Error::AmountOverflow), TokenAmount::new(U256::max_value(), 6).unwrap().checked_add(amount(1, 6)));
+	assert_eq!(Err(ERC20Error::DecimalsMismatch), a.checked_add(amount(1, 18)));
+	assert_eq!(Ok(amount(4_500_000, 6)), a.checked_mul(3.into()));
+	assert_eq!(Ok(amount(500_000, 6)), a.checked_div(3.into()));
+	assert_eq!(Err(ERC20Error::DivisionByZero), a.checked_div(U256::zero()));
+	assert!(a.checked_sub(a).unwrap().is_zero());
+}
+
+#[test]
+fn serialize_as_string() {
+	let value = amount(1_000_000_000, 6);
+	assert_eq!(r#""1000.000000""#, serde_json::to_string(&value).unwrap());
+	assert_eq!(value, serde_json::from_str::<TokenAmount>(r#""1000.000000""#).unwrap());
+	assert!(serde_json::from_str::<TokenAmount>(r#""1e3""#).is_err());
+	assert!(serde_json::from_str::<TokenAmount>("1000").is_err());
+}
+
+#[test]
+fn decimals_limit() {
+	assert_eq!(Err(ERC20Error::PrecisionLoss), TokenAmount::new(1.into(), 78));
+	let max = amount(1, 77);
+	assert_eq!(max, serde_json::from_str(&serde_json::to_string(&max).unwrap()).unwrap());
+	assert!(serde_json::to_string(&TokenAmount { raw: U256::one(), decimals: 78 }).is_err());
+}
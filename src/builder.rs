@@ -0,0 +1,147 @@
+//! Offline ERC20 transfer transaction building.
+
+use crate::{
+	envelope::GasPricing,
+	erc20::ERC20Call,
+	raw::SignedTransaction,
+	signature,
+	ERC20Error,
+};
+use web3::{
+	signing::Key,
+	types::{
+		Transaction,
+		H160,
+		U256,
+	},
+};
+
+/// Builds and signs ERC20 transfer transactions without a node.
+///
+/// Legacy gas pricing produces an EIP-155 legacy transaction and dynamic fees an EIP-1559 transaction.
+///
+/// ```
+/// use erc20::{
+///     builder::TransferBuilder,
+///     envelope::GasPricing,
+///     erc20::ERC20Call,
+///     raw::SignedTransaction,
+///     transaction::TransactionAndTransferType,
+///     transfer::Transfer,
+/// };
+/// use secp256k1::SecretKey;
+/// use std::convert::TryFrom;
+/// use web3::{
+///     signing::{
+///         Key,
+///         SecretKeyRef,
+///     },
+///     types::H160,
+/// };
+///
+/// let key = SecretKey::from_slice(&[0x46; 32]).unwrap();
+/// let contract = H160::from_low_u64_be(0x70c3e);
+/// let to = H160::from_low_u64_be(1);
+/// let gas_pricing = GasPricing::DynamicFee {
+///     max_fee_per_gas: 100_000_000_000u64.into(),
+///     max_priority_fee_per_gas: 2_000_000_000u64.into(),
+/// };
+/// let call = ERC20Call::Transfer { to, value: 10.into() };
+/// let signed = TransferBuilder::new(contract, call, 1, 60_000.into(), gas_pricing).unwrap()
+///     .with_nonce(3.into())
+///     .sign(SecretKeyRef::new(&key))
+///     .unwrap();
+///
+/// // The raw bytes are ready to be broadcast and can be parsed back.
+/// let raw = signed.transaction.raw.unwrap();
+/// let parsed = SignedTransaction::try_from(raw).unwrap();
+/// let transfer = TransactionAndTransferType::try_from(parsed.transaction).unwrap();
+/// assert_eq!(to, transfer.to());
+/// assert_eq!(SecretKeyRef::new(&key).address(), transfer.from());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferBuilder {
+	contract: H160,
+	call: ERC20Call,
+	chain_id: u64,
+	nonce: U256,
+	gas: U256,
+	gas_pricing: GasPricing,
+}
+
+impl TransferBuilder {
+	/// Creates a builder with zero nonce.
+	///
+	/// # Arguments
+	///
+	/// * `contract` - The token contract address.
+	/// * `call` - The transfer call, any other call is a `NoTransferTransaction` error.
+	/// * `chain_id` - The chain id the transaction is signed for.
+	/// * `gas` - The gas limit.
+	/// * `gas_pricing` - The legacy gas price or the EIP-1559 fees, deciding the transaction type.
+	///
+	pub fn new(
+		contract: H160,
+		call: ERC20Call,
+		chain_id: u64,
+		gas: U256,
+		gas_pricing: GasPricing,
+	) -> Result<Self, ERC20Error> {
+		match call {
+			ERC20Call::Transfer { .. } => Ok(Self {
+				contract,
+				call,
+				chain_id,
+				nonce: U256::zero(),
+				gas,
+				gas_pricing,
+			}),
+			_ => Err(ERC20Error::NoTransferTransaction),
+		}
+	}
+
+	/// Sets the sender nonce.
+	///
+	/// # Arguments
+	///
+	/// * `nonce` - The number of transactions sent by the sender before this one.
+	///
+	pub fn with_nonce(mut self, nonce: U256) -> Self {
+		self.nonce = nonce;
+		self
+	}
+
+	/// Returns the unsigned transaction.
+	pub fn transaction(&self) -> Transaction {
+		let transaction = Transaction {
+			nonce: self.nonce,
+			gas: self.gas,
+			to: Some(self.contract),
+			input: self.call.encode(),
+			..Default::default()
+		};
+		match self.gas_pricing {
+			GasPricing::Legacy { gas_price } => Transaction {
+				gas_price: Some(gas_price),
+				..transaction
+			},
+			GasPricing::DynamicFee { max_fee_per_gas, max_priority_fee_per_gas } => Transaction {
+				transaction_type: Some(2.into()),
+				max_fee_per_gas: Some(max_fee_per_gas),
+				max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+				access_list: Some(Vec::new()),
+				..transaction
+			},
+		}
+	}
+
+	/// Signs the transaction, whose raw bytes are in `transaction.raw`.
+	///
+	/// # Arguments
+	///
+	/// * `key` - The sender private key.
+	///
+	pub fn sign<K: Key>(&self, key: K) -> Result<SignedTransaction, ERC20Error> {
+		signature::sign(self.transaction(), self.chain_id, key)
+	}
+}
@@ -0,0 +1,119 @@
+use crate::{
+	builder::TransferBuilder,
+	envelope::{
+		GasPricing,
+		TransactionEnvelope,
+		TransactionType,
+	},
+	erc20::ERC20Call,
+	raw::SignedTransaction,
+	transaction::TransactionAndTransferType,
+	transfer::{
+		Transfer,
+		TransferType,
+	},
+	ERC20Error,
+};
+use secp256k1::SecretKey;
+use std::convert::TryFrom;
+use web3::{
+	signing::{
+		Key,
+		SecretKeyRef,
+	},
+	types::{
+		H160,
+		U256,
+	},
+};
+
+fn key() -> SecretKey {
+	SecretKey::from_slice(&[0x46; 32]).unwrap()
+}
+
+fn contract() -> H160 {
+	H160::from_low_u64_be(0x70c3e)
+}
+
+fn recipient() -> H160 {
+	H160::from_low_u64_be(1)
+}
+
+// EIP-1559 transfer from `builder` with `dynamic_fee`, signed with web3's `Accounts::sign_transaction`.
+const DYNAMIC_FEE_RAW: &str = "02f8b00107847735940085174876e80082ea60940000000000000000000000000000000000070c3e80b844a9059cbb000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000f4240c001a05f893639f1065b4c95059a5c1db07dacc920d8caa232706860f29ca0e5c94b0da06844ac61b44ae0763c894ba965e75931577588bee465c53805434cd2e0a2d801";
+
+fn dynamic_fee() -> GasPricing {
+	GasPricing::DynamicFee {
+		max_fee_per_gas: 100_000_000_000u64.into(),
+		max_priority_fee_per_gas: 2_000_000_000u64.into(),
+	}
+}
+
+fn builder(gas_pricing: GasPricing) -> TransferBuilder {
+	let call = ERC20Call::Transfer { to: recipient(), value: 1_000_000.into() };
+	TransferBuilder::new(contract(), call, 1, 60_000.into(), gas_pricing)
+		.unwrap()
+		.with_nonce(7.into())
+}
+
+fn parse(signed: &SignedTransaction) -> (SignedTransaction, TransactionAndTransferType) {
+	let parsed = SignedTransaction::try_from(signed.transaction.raw.clone().unwrap()).unwrap();
+	let transfer = TransactionAndTransferType::try_from(parsed.transaction.clone()).unwrap();
+	(parsed, transfer)
+}
+
+#[test]
+fn sign_legacy_transfer() {
+	let signed = builder(GasPricing::Legacy { gas_price: 20_000_000_000u64.into() })
+		.sign(SecretKeyRef::new(&key()))
+		.unwrap();
+	let (parsed, transfer) = parse(&signed);
+	assert_eq!(signed, parsed);
+	assert_eq!(Some(37.into()), parsed.transaction.v);
+
+	let envelope = TransactionEnvelope::try_from(&parsed.transaction).unwrap();
+	assert_eq!(TransactionType::Legacy, envelope.transaction_type);
+	assert_eq!(TransferType::ERC20, transfer.transfer_type());
+	assert_eq!(SecretKeyRef::new(&key()).address(), transfer.from());
+	assert_eq!(recipient(), transfer.to());
+	assert_eq!(Some(contract()), transfer.contract());
+	assert_eq!(U256::from(1_000_000), transfer.value());
+}
+
+#[test]
+fn sign_dynamic_fee_transfer() {
+	let signed = builder(dynamic_fee()).sign(SecretKeyRef::new(&key())).unwrap();
+	let (parsed, transfer) = parse(&signed);
+	assert_eq!(signed, parsed);
+	assert_eq!(Some(hex::decode(DYNAMIC_FEE_RAW).unwrap().into()), signed.transaction.raw);
+	assert_eq!(Some(1), parsed.chain_id);
+	assert_eq!(U256::from(7), parsed.transaction.nonce);
+	assert_eq!(U256::from(60_000), parsed.transaction.gas);
+
+	let envelope = TransactionEnvelope::try_from(&parsed.transaction).unwrap();
+	assert_eq!(TransactionType::DynamicFee, envelope.transaction_type);
+	assert_eq!(dynamic_fee(), envelope.gas_pricing);
+	assert_eq!(SecretKeyRef::new(&key()).address(), transfer.from());
+	assert_eq!(recipient(), transfer.to());
+	assert_eq!(U256::from(1_000_000), transfer.value());
+}
+
+#[test]
+fn deterministic_signature() {
+	let builder = builder(GasPricing::Legacy { gas_price: 1.into() });
+	assert_eq!(
+		builder.sign(SecretKeyRef::new(&key())).unwrap(),
+		builder.sign(SecretKeyRef::new(&key())).unwrap(),
+	);
+	assert_ne!(
+		builder.sign(SecretKeyRef::new(&key())).unwrap().transaction.hash,
+		builder.with_nonce(8.into()).sign(SecretKeyRef::new(&key())).unwrap().transaction.hash,
+	);
+}
+
+#[test]
+fn not_a_transfer() {
+	let call = ERC20Call::Approve { spender: recipient(), value: 1.into() };
+	let gas_pricing = GasPricing::Legacy { gas_price: 1.into() };
+	assert_eq!(Err(ERC20Error::NoTransferTransaction), TransferBuilder::new(contract(), call, 1, 60_000.into(), gas_pricing));
+}
@@ -0,0 +1,18 @@
+//! Chain ids of the networks with known token deployments.
+//!
+//! The same token usually has a different address on each network, and the same address may be a
+//! different token, so addresses are only meaningful along with their chain id (EIP-155).
+
+/// Ethereum mainnet.
+pub const MAINNET: u64 = 1;
+/// Optimism.
+pub const OPTIMISM: u64 = 10;
+/// BNB Smart Chain, where BNB is the native asset.
+pub const BSC: u64 = 56;
+/// Polygon PoS.
+pub const POLYGON: u64 = 137;
+/// Arbitrum One.
+pub const ARBITRUM: u64 = 42161;
+
+/// The number of decimals of the native asset of every chain above.
+pub const NATIVE_DECIMALS: u8 = 18;
@@ -0,0 +1,146 @@
+//! Transaction classification.
+
+use crate::{
+	signature,
+	transaction::ParsedTransaction,
+};
+use std::collections::HashMap;
+use web3::types::{
+	H160,
+	Transaction,
+};
+
+/// Source of information about which addresses hold contract code.
+///
+/// It is used by the `TransactionClassifier` to tell an Ether transfer carrying a memo in its
+/// input, which goes to an externally owned account, from an actual contract invocation.
+pub trait CodeOracle {
+	/// Returns `Some(true)` if the address holds contract code, `Some(false)` if it is an
+	/// externally owned account, or `None` if it is unknown.
+	fn has_code(&self, address: &H160) -> Option<bool>;
+}
+
+impl CodeOracle for HashMap<H160, bool> {
+	fn has_code(&self, address: &H160) -> Option<bool> {
+		self.get(address).copied()
+	}
+}
+
+/// Classifies web3 transactions into `ParsedTransaction`.
+///
+/// ```
+/// use erc20::{
+///     classifier::TransactionClassifier,
+///     transaction::ParsedTransaction,
+/// };
+/// use std::collections::HashMap;
+/// use web3::types::{
+///     Bytes,
+///     H160,
+///     Transaction,
+/// };
+///
+/// let recipient = H160::random();
+/// let transaction = Transaction {
+///     from: Some(H160::random()),
+///     to: Some(recipient),
+///     value: 1.into(),
+///     input: Bytes(b"invoice 42".to_vec()),
+///     ..Default::default()
+/// };
+///
+/// // Without knowing the recipient it has to be taken as a contract invocation.
+/// let parsed = TransactionClassifier::new().with_chain_id(1).classify(transaction.clone());
+/// assert!(matches!(parsed, ParsedTransaction::ContractInvocation(Some(1), _)));
+///
+/// // Once the recipient is known to have no code, the input is just a memo.
+/// let oracle: HashMap<H160, bool> = vec![(recipient, false)].into_iter().collect();
+/// let parsed = TransactionClassifier::with_oracle(&oracle).classify(transaction);
+/// assert!(matches!(parsed, ParsedTransaction::EthereumTransfer(None, _)));
+/// ```
+#[derive(Clone, Copy)]
+pub struct TransactionClassifier<'a> {
+	oracle: Option<&'a dyn CodeOracle>,
+	chain_id: Option<u64>,
+}
+
+impl<'a> Default for TransactionClassifier<'a> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<'a> TransactionClassifier<'a> {
+	/// Creates a classifier with no code information, every non-empty input to a recipient is taken
+	/// as a contract invocation.
+	pub fn new() -> Self {
+		Self {
+			oracle: None,
+			chain_id: None,
+		}
+	}
+
+	/// Creates a classifier that queries `oracle` for the recipients with non-empty input.
+	///
+	/// # Arguments
+	///
+	/// * `oracle` - Source of contract code information.
+	///
+	pub fn with_oracle(oracle: &'a dyn CodeOracle) -> Self {
+		Self {
+			oracle: Some(oracle),
+			chain_id: None,
+		}
+	}
+
+	/// Sets the chain id of the classified transactions. Otherwise it is only known for legacy
+	/// transactions signed with EIP-155 replay protection.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	///
+	pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+		self.chain_id = Some(chain_id);
+		self
+	}
+
+	/// Classifies the transaction.
+	///
+	/// # Arguments
+	///
+	/// * `transaction` - The transaction to be classified.
+	///
+	pub fn classify(&self, transaction: Transaction) -> ParsedTransaction {
+		let chain_id = self.chain_id.or_else(|| signature::legacy_chain_id(&transaction));
+		match transaction.to {
+			None => if transaction.input.0.is_empty() {
+				ParsedTransaction::Other(chain_id, transaction)
+			} else {
+				ParsedTransaction::ContractCreation(chain_id, transaction)
+			},
+			Some(to) => if transaction.input.0.is_empty() || self.is_externally_owned(&to) {
+				Self::classify_ether_send(chain_id, to, transaction)
+			} else {
+				ParsedTransaction::ContractInvocation(chain_id, transaction.into())
+			},
+		}
+	}
+
+	fn is_externally_owned(&self, address: &H160) -> bool {
+		match self.oracle {
+			Some(oracle) => oracle.has_code(address) == Some(false),
+			None => false,
+		}
+	}
+
+	fn classify_ether_send(chain_id: Option<u64>, to: H160, transaction: Transaction) -> ParsedTransaction {
+		if transaction.value.is_zero() {
+			ParsedTransaction::ZeroValuePing(chain_id, transaction)
+		} else if transaction.from == Some(to) {
+			ParsedTransaction::SelfTransfer(chain_id, transaction)
+		} else {
+			ParsedTransaction::EthereumTransfer(chain_id, transaction)
+		}
+	}
+}
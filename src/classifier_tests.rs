@@ -0,0 +1,168 @@
+use crate::{
+	chain,
+	classifier::TransactionClassifier,
+	erc20::{
+		ContractAddress,
+		ERC20Method,
+	},
+	transaction::{
+		ParsedTransaction,
+		TransactionAndTransferType,
+		TransactionContractInvocation,
+	},
+	transfer::Transfer,
+	ERC20Error,
+};
+use std::{
+	collections::HashMap,
+	convert::TryInto,
+	str::FromStr,
+};
+use web3::types::{
+	Bytes,
+	H160,
+	Transaction,
+	U256,
+};
+
+fn transaction(from: H160, to: Option<H160>, value: u64, input: &str) -> Transaction {
+	Transaction {
+		from: Some(from),
+		to,
+		value: value.into(),
+		input: Bytes(hex::decode(input).unwrap()),
+		..Default::default()
+	}
+}
+
+#[test]
+fn ether_transfer() {
+	let (from, to) = (H160::random(), H160::random());
+	let parsed: ParsedTransaction = transaction(from, Some(to), 10, "").into();
+	assert!(matches!(parsed, ParsedTransaction::EthereumTransfer(_, _)));
+
+	let resp: Result<TransactionAndTransferType, ERC20Error> = parsed.try_into();
+	assert!(resp.is_ok());
+	let resp = resp.unwrap();
+	assert_eq!(from, resp.from());
+	assert_eq!(to, resp.to());
+	assert_eq!(U256::from(10), resp.value());
+	assert_eq!(None, resp.contract());
+	assert!((&resp as &dyn Transfer).is_ethereum());
+}
+
+#[test]
+fn self_transfer() {
+	let from = H160::random();
+	let parsed: ParsedTransaction = transaction(from, Some(from), 10, "").into();
+	assert!(matches!(parsed, ParsedTransaction::SelfTransfer(_, _)));
+
+	let resp: TransactionAndTransferType = parsed.try_into().unwrap();
+	assert_eq!(from, resp.from());
+	assert_eq!(from, resp.to());
+}
+
+#[test]
+fn zero_value_ping() {
+	let from = H160::random();
+	let parsed: ParsedTransaction = transaction(from, Some(H160::random()), 0, "").into();
+	assert!(matches!(parsed, ParsedTransaction::ZeroValuePing(_, _)));
+
+	// A zero value self-send is used to cancel pending transactions.
+	let parsed: ParsedTransaction = transaction(from, Some(from), 0, "").into();
+	assert!(matches!(parsed, ParsedTransaction::ZeroValuePing(_, _)));
+
+	let resp: Result<TransactionAndTransferType, ERC20Error> = parsed.try_into();
+	assert_eq!(ERC20Error::NoTransferTransaction, resp.err().unwrap());
+}
+
+#[test]
+fn erc20_contract_invocation() {
+	let serialized_str = "a9059cbb0000000000000000000000006748f50f686bfbca6fe8ad62b22228b87f31ff2b00000000000000000000000000000000000000000000003635c9adc5dea00000";
+	let parsed: ParsedTransaction = transaction(H160::random(), Some(H160::random()), 0, serialized_str).into();
+	match parsed {
+		ParsedTransaction::ContractInvocation(_, TransactionContractInvocation::ERC20(method, _)) => {
+			assert_eq!(ERC20Method::Transfer, method);
+		}
+		_ => panic!("Unexpected classification {:?}", parsed),
+	}
+}
+
+#[test]
+fn other_contract_invocation() {
+	let parsed: ParsedTransaction = transaction(H160::random(), Some(H160::random()), 5, "d0e30db0").into();
+	assert!(matches!(parsed, ParsedTransaction::ContractInvocation(_, TransactionContractInvocation::Other(_))));
+
+	let resp: Result<TransactionAndTransferType, ERC20Error> = parsed.try_into();
+	assert_eq!(ERC20Error::NoTransferTransaction, resp.err().unwrap());
+}
+
+#[test]
+fn contract_creation() {
+	let parsed: ParsedTransaction = transaction(H160::random(), None, 0, "6080604052").into();
+	assert!(matches!(parsed, ParsedTransaction::ContractCreation(_, _)));
+}
+
+#[test]
+fn other() {
+	let parsed: ParsedTransaction = transaction(H160::random(), None, 0, "").into();
+	assert!(matches!(parsed, ParsedTransaction::Other(_, _)));
+	assert_eq!(ParsedTransaction::Other(None, Default::default()), ParsedTransaction::default());
+}
+
+#[test]
+fn ether_transfer_with_memo() {
+	let (from, to) = (H160::random(), H160::random());
+	let memo = hex::encode(b"invoice 42");
+	let oracle: HashMap<H160, bool> = vec![(to, false)].into_iter().collect();
+	let classifier = TransactionClassifier::with_oracle(&oracle);
+
+	let parsed = classifier.classify(transaction(from, Some(to), 10, &memo));
+	assert!(matches!(parsed, ParsedTransaction::EthereumTransfer(_, _)));
+	let resp: TransactionAndTransferType = parsed.try_into().unwrap();
+	assert_eq!(to, resp.to());
+	assert_eq!(U256::from(10), resp.value());
+
+	let parsed = classifier.classify(transaction(from, Some(to), 0, &memo));
+	assert!(matches!(parsed, ParsedTransaction::ZeroValuePing(_, _)));
+}
+
+#[test]
+fn memo_needs_known_externally_owned_account() {
+	let (contract, unknown) = (H160::random(), H160::random());
+	let oracle: HashMap<H160, bool> = vec![(contract, true)].into_iter().collect();
+	let classifier = TransactionClassifier::with_oracle(&oracle);
+
+	let parsed = classifier.classify(transaction(H160::random(), Some(contract), 10, "d0e30db0"));
+	assert!(matches!(parsed, ParsedTransaction::ContractInvocation(_, _)));
+
+	let parsed = classifier.classify(transaction(H160::random(), Some(unknown), 10, "d0e30db0"));
+	assert!(matches!(parsed, ParsedTransaction::ContractInvocation(_, _)));
+}
+
+#[test]
+fn chain_transfers() {
+	let polygon_usdc = H160::from_str("3c499c542cef5e3811e1192ce70d8cc03d5c3359").unwrap();
+	let serialized_str = "a9059cbb0000000000000000000000006748f50f686bfbca6fe8ad62b22228b87f31ff2b00000000000000000000000000000000000000000000000000000000000f4240";
+	let transaction = transaction(H160::random(), Some(polygon_usdc), 0, serialized_str);
+
+	let parsed = TransactionClassifier::new().with_chain_id(chain::POLYGON).classify(transaction.clone());
+	assert_eq!(Some(chain::POLYGON), parsed.chain_id());
+	let resp: TransactionAndTransferType = parsed.try_into().unwrap();
+	assert_eq!(Some(chain::POLYGON), resp.chain_id());
+	assert_eq!(Some(ContractAddress::USDC), resp.contract_address());
+
+	// The same address is not USDC on mainnet.
+	let parsed = TransactionClassifier::new().with_chain_id(chain::MAINNET).classify(transaction.clone());
+	assert_eq!(Some(chain::MAINNET), parsed.chain_id());
+	let resp: TransactionAndTransferType = parsed.try_into().unwrap();
+	assert_eq!(Some(chain::MAINNET), resp.chain_id());
+	assert_eq!(Some(ContractAddress::Unidentified(polygon_usdc)), resp.contract_address());
+
+	// Nor is it identified on an unknown chain.
+	let parsed: ParsedTransaction = transaction.into();
+	assert_eq!(None, parsed.chain_id());
+	let resp: TransactionAndTransferType = parsed.try_into().unwrap();
+	assert_eq!(None, resp.chain_id());
+	assert_eq!(Some(ContractAddress::Unidentified(polygon_usdc)), resp.contract_address());
+}
@@ -0,0 +1,407 @@
+//! EIP-712 typed structured data hashing.
+
+use crate::{
+	abi::Token,
+	selector,
+	util::FixedNumberToBytes,
+	ERC20Error,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::collections::{
+	BTreeMap,
+	BTreeSet,
+};
+use web3::{
+	signing::keccak256,
+	types::{
+		H160,
+		H256,
+		U256,
+	},
+};
+
+/// EIP-712 domain, binding a signature to a contract on a chain.
+///
+/// Every field is optional, the ones set are part of the `EIP712Domain` type.
+///
+/// ```
+/// use erc20::eip712::Domain;
+/// use std::str::FromStr;
+/// use web3::types::{
+///     H160,
+///     H256,
+/// };
+///
+/// let dai = H160::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap();
+/// let domain = Domain::new("Dai Stablecoin", "1", 1, dai);
+/// assert_eq!(
+///     H256::from_str("dbb8cf42e1ecb028be3f3dbc922e1d878b963f411dc388ced501601c60f7c6f7").unwrap(),
+///     domain.separator(),
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Domain {
+	/// The name of the signing domain, e.g. the token name.
+	pub name: Option<String>,
+	/// The version of the signing domain.
+	pub version: Option<String>,
+	/// The chain id.
+	pub chain_id: Option<U256>,
+	/// The address of the contract verifying the signature.
+	pub verifying_contract: Option<H160>,
+	/// The disambiguating salt.
+	pub salt: Option<H256>,
+}
+
+impl Domain {
+	/// Creates the domain used by most tokens.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The token name.
+	/// * `version` - The version, e.g. `"1"`.
+	/// * `chain_id` - The chain id.
+	/// * `verifying_contract` - The token contract address.
+	///
+	pub fn new(name: &str, version: &str, chain_id: u64, verifying_contract: H160) -> Self {
+		Self {
+			name: Some(name.to_string()),
+			version: Some(version.to_string()),
+			chain_id: Some(chain_id.into()),
+			verifying_contract: Some(verifying_contract),
+			salt: None,
+		}
+	}
+
+	/// Returns the `EIP712Domain` type with the fields set, e.g.
+	/// `EIP712Domain(string name,uint256 chainId,address verifyingContract)`.
+	pub fn encode_type(&self) -> String {
+		let mut fields = Vec::new();
+		if self.name.is_some() {
+			fields.push("string name");
+		}
+		if self.version.is_some() {
+			fields.push("string version");
+		}
+		if self.chain_id.is_some() {
+			fields.push("uint256 chainId");
+		}
+		if self.verifying_contract.is_some() {
+			fields.push("address verifyingContract");
+		}
+		if self.salt.is_some() {
+			fields.push("bytes32 salt");
+		}
+		format!("EIP712Domain({})", fields.join(","))
+	}
+
+	/// Returns the domain separator, `hashStruct(eip712Domain)`.
+	pub fn separator(&self) -> H256 {
+		let mut encoder: FixedNumberToBytes = Default::default();
+		encoder.push_vec(&keccak256(self.encode_type().as_bytes()));
+		if let Some(name) = &self.name {
+			encoder.push_vec(&keccak256(name.as_bytes()));
+		}
+		if let Some(version) = &self.version {
+			encoder.push_vec(&keccak256(version.as_bytes()));
+		}
+		if let Some(chain_id) = &self.chain_id {
+			encoder.push_u256(chain_id);
+		}
+		if let Some(verifying_contract) = &self.verifying_contract {
+			encoder.push_h160(verifying_contract);
+		}
+		if let Some(salt) = &self.salt {
+			encoder.push_h256(salt);
+		}
+		H256(keccak256(&Vec::from(encoder)))
+	}
+}
+
+/// Returns the digest signed for a message, `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+///
+/// # Arguments
+///
+/// * `domain` - The signing domain.
+/// * `struct_hash` - The hash of the message, `hashStruct(message)`.
+///
+pub fn digest(domain: &Domain, struct_hash: &H256) -> H256 {
+	let mut data = vec![0x19, 0x01];
+	data.extend_from_slice(domain.separator().as_bytes());
+	data.extend_from_slice(struct_hash.as_bytes());
+	H256(keccak256(&data))
+}
+
+/// Member of an EIP-712 struct type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Member {
+	/// The member name.
+	pub name: String,
+	/// The member type, an atomic type as `uint256`, `string`, or `bytes`, a struct type name, or an
+	/// array of them, e.g. `Person[]` or `address[2]`.
+	#[serde(rename = "type")]
+	pub member_type: String,
+}
+
+impl Member {
+	/// Creates a struct member.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The member name.
+	/// * `member_type` - The member type.
+	///
+	pub fn new(name: &str, member_type: &str) -> Self {
+		Self {
+			name: name.to_string(),
+			member_type: member_type.to_string(),
+		}
+	}
+}
+
+/// EIP-712 struct types by name, as the `types` of `eth_signTypedData`.
+///
+/// Struct values are `Token::Tuple`s with a token for each member in declaration order, arrays are
+/// `Token::Array`s or `Token::FixedArray`s.
+///
+/// ```
+/// use erc20::{
+///     abi::Token,
+///     eip712::{
+///         Member,
+///         Schema,
+///     },
+/// };
+/// use web3::types::H160;
+///
+/// let schema = Schema::new()
+///     .with_type("Person", vec![Member::new("name", "string"), Member::new("wallet", "address")])
+///     .with_type("Mail", vec![
+///         Member::new("from", "Person"),
+///         Member::new("to", "Person[]"),
+///         Member::new("contents", "string"),
+///     ]);
+/// assert_eq!(
+///     Ok("Mail(Person from,Person[] to,string contents)Person(string name,address wallet)".to_string()),
+///     schema.encode_type("Mail"),
+/// );
+///
+/// let person = |name: &str, wallet: u64| Token::Tuple(vec![
+///     Token::String(name.to_string()),
+///     Token::Address(H160::from_low_u64_be(wallet)),
+/// ]);
+/// let mail = Token::Tuple(vec![
+///     person("Cow", 1),
+///     Token::Array(vec![person("Bob", 2), person("Alice", 3)]),
+///     Token::String("Hello!".to_string()),
+/// ]);
+/// assert!(schema.hash_struct("Mail", &mail).is_ok());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Schema {
+	types: BTreeMap<String, Vec<Member>>,
+}
+
+impl Schema {
+	/// Creates an empty schema.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Adds a struct type, replacing any type with the same name.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The struct name.
+	/// * `members` - The struct members, in declaration order.
+	///
+	pub fn with_type(mut self, name: &str, members: Vec<Member>) -> Self {
+		self.types.insert(name.to_string(), members);
+		self
+	}
+
+	/// Returns the members of a struct type, if it is defined.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The struct name.
+	///
+	pub fn members(&self, name: &str) -> Option<&[Member]> {
+		self.types.get(name).map(|it| it.as_slice())
+	}
+
+	/// Returns the struct type encoding, `encodeType`: the struct followed by the structs it references,
+	/// sorted by name.
+	///
+	/// # Arguments
+	///
+	/// * `primary_type` - The struct name.
+	///
+	pub fn encode_type(&self, primary_type: &str) -> Result<String, ERC20Error> {
+		let mut dependencies = BTreeSet::new();
+		self.collect_dependencies(primary_type, &mut dependencies)?;
+		dependencies.remove(primary_type);
+		let mut resp = self.encode_single_type(primary_type)?;
+		for dependency in dependencies {
+			resp.push_str(&self.encode_single_type(dependency)?);
+		}
+		Ok(resp)
+	}
+
+	/// Returns the struct type hash, `typeHash`.
+	///
+	/// # Arguments
+	///
+	/// * `primary_type` - The struct name.
+	///
+	pub fn type_hash(&self, primary_type: &str) -> Result<H256, ERC20Error> {
+		Ok(H256(keccak256(self.encode_type(primary_type)?.as_bytes())))
+	}
+
+	/// Returns the struct data encoding, `encodeData`: a word for every member.
+	///
+	/// # Arguments
+	///
+	/// * `primary_type` - The struct name.
+	/// * `value` - The struct value.
+	///
+	pub fn encode_data(&self, primary_type: &str, value: &Token) -> Result<Vec<u8>, ERC20Error> {
+		let members = self.members(primary_type).ok_or(ERC20Error::UnexpectedType)?;
+		let values = match value {
+			Token::Tuple(values) => values,
+			_ => return Err(ERC20Error::UnexpectedType),
+		};
+		if values.len() != members.len() {
+			return Err(ERC20Error::UnexpectedSize);
+		}
+		let mut encoder: FixedNumberToBytes = Default::default();
+		for (member, value) in members.iter().zip(values) {
+			self.push_value(&mut encoder, &member.member_type, value)?;
+		}
+		Ok(encoder.into())
+	}
+
+	/// Returns the struct hash, `hashStruct`: `keccak256(typeHash ‖ encodeData(value))`.
+	///
+	/// # Arguments
+	///
+	/// * `primary_type` - The struct name.
+	/// * `value` - The struct value.
+	///
+	pub fn hash_struct(&self, primary_type: &str, value: &Token) -> Result<H256, ERC20Error> {
+		let mut data = self.type_hash(primary_type)?.as_bytes().to_vec();
+		data.extend_from_slice(&self.encode_data(primary_type, value)?);
+		Ok(H256(keccak256(&data)))
+	}
+
+	/// Returns the digest signed for a struct value.
+	///
+	/// # Arguments
+	///
+	/// * `domain` - The signing domain.
+	/// * `primary_type` - The struct name.
+	/// * `value` - The struct value.
+	///
+	pub fn digest(&self, domain: &Domain, primary_type: &str, value: &Token) -> Result<H256, ERC20Error> {
+		Ok(digest(domain, &self.hash_struct(primary_type, value)?))
+	}
+
+	fn encode_single_type(&self, name: &str) -> Result<String, ERC20Error> {
+		let members = self.members(name).ok_or(ERC20Error::UnexpectedType)?;
+		let members = members.iter()
+			.map(|it| format!("{} {}", it.member_type, it.name))
+			.collect::<Vec<String>>();
+		Ok(format!("{}({})", name, members.join(",")))
+	}
+
+	fn collect_dependencies<'a>(&'a self, name: &'a str, dependencies: &mut BTreeSet<&'a str>) -> Result<(), ERC20Error> {
+		if !dependencies.insert(name) {
+			return Ok(());
+		}
+		let members = self.members(name).ok_or(ERC20Error::UnexpectedType)?;
+		for member in members {
+			let base_type = base_type(&member.member_type);
+			if self.types.contains_key(base_type) {
+				self.collect_dependencies(base_type, dependencies)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn push_value(&self, encoder: &mut FixedNumberToBytes, value_type: &str, value: &Token) -> Result<(), ERC20Error> {
+		if let Some((item_type, size)) = array_type(value_type)? {
+			let items = match value {
+				Token::Array(items) | Token::FixedArray(items) => items,
+				_ => return Err(ERC20Error::UnexpectedType),
+			};
+			match size {
+				Some(size) if size != items.len() => return Err(ERC20Error::UnexpectedSize),
+				_ => {}
+			}
+			let mut item_encoder: FixedNumberToBytes = Default::default();
+			for item in items {
+				self.push_value(&mut item_encoder, item_type, item)?;
+			}
+			encoder.push_vec(&keccak256(&Vec::from(item_encoder)));
+			return Ok(());
+		}
+		if self.types.contains_key(value_type) {
+			encoder.push_h256(&self.hash_struct(value_type, value)?);
+			return Ok(());
+		}
+		match (value_type, value) {
+			("address", Token::Address(address)) => encoder.push_h160(address),
+			("bool", Token::Bool(value)) => encoder.push_bool(*value),
+			("string", Token::String(value)) => encoder.push_vec(&keccak256(value.as_bytes())),
+			("bytes", Token::Bytes(value)) => encoder.push_vec(&keccak256(value)),
+			(_, Token::Uint(value)) if is_integer_type(value_type, "uint") => encoder.push_u256(value),
+			(_, Token::Int(value)) if is_integer_type(value_type, "int") => encoder.push_u256(value),
+			(_, Token::FixedBytes(value)) if value_type.starts_with("bytes") && value_type != "bytes" => {
+				let size: usize = value_type["bytes".len()..].parse().map_err(|_| ERC20Error::UnexpectedType)?;
+				if size == 0 || size > 32 {
+					return Err(ERC20Error::UnexpectedType);
+				}
+				if value.len() != size {
+					return Err(ERC20Error::UnexpectedSize);
+				}
+				encoder.push_vec_padded(value);
+			}
+			_ => return Err(ERC20Error::UnexpectedType),
+		}
+		Ok(())
+	}
+}
+
+/// Checks if the type is an integer type with the prefix, `uint` or `int`, and a size in bits.
+fn is_integer_type(value_type: &str, prefix: &str) -> bool {
+	value_type.strip_prefix(prefix).is_some_and(|size| selector::is_size(size, 8, 256, 8))
+}
+
+/// Returns the type without array suffixes, e.g. `Person` for `Person[][2]`.
+fn base_type(value_type: &str) -> &str {
+	match value_type.find('[') {
+		Some(index) => &value_type[..index],
+		None => value_type,
+	}
+}
+
+/// Returns the item type and the size, if fixed, of an array type.
+fn array_type(value_type: &str) -> Result<Option<(&str, Option<usize>)>, ERC20Error> {
+	if !value_type.ends_with(']') {
+		return Ok(None);
+	}
+	let index = value_type.rfind('[').ok_or(ERC20Error::UnexpectedType)?;
+	let size = &value_type[index + 1..value_type.len() - 1];
+	let size = if size.is_empty() {
+		None
+	} else {
+		Some(size.parse().map_err(|_| ERC20Error::UnexpectedType)?)
+	};
+	Ok(Some((&value_type[..index], size)))
+}
@@ -0,0 +1,280 @@
+use crate::{
+	abi::Token,
+	eip712::{
+		digest,
+		Domain,
+		Member,
+		Schema,
+	},
+	util::FixedNumberToBytes,
+	ERC20Error,
+};
+use secp256k1::SecretKey;
+use std::str::FromStr;
+use web3::{
+	signing::{
+		keccak256,
+		Key,
+		SecretKeyRef,
+	},
+	types::{
+		H160,
+		H256,
+		U256,
+	},
+};
+
+// Example of the EIP, from `eth_signTypedData`.
+const MAIL_TYPES: &str = r#"{
+	"EIP712Domain": [
+		{ "name": "name", "type": "string" },
+		{ "name": "version", "type": "string" },
+		{ "name": "chainId", "type": "uint256" },
+		{ "name": "verifyingContract", "type": "address" }
+	],
+	"Person": [
+		{ "name": "name", "type": "string" },
+		{ "name": "wallet", "type": "address" }
+	],
+	"Mail": [
+		{ "name": "from", "type": "Person" },
+		{ "name": "to", "type": "Person" },
+		{ "name": "contents", "type": "string" }
+	]
+}"#;
+
+fn mail_domain() -> Domain {
+	Domain::new("Ether Mail", "1", 1, H160::from_str("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap())
+}
+
+fn person(name: &str, wallet: &str) -> Token {
+	Token::Tuple(vec![
+		Token::String(name.to_string()),
+		Token::Address(H160::from_str(wallet).unwrap()),
+	])
+}
+
+fn mail() -> Token {
+	Token::Tuple(vec![
+		person("Cow", "CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+		person("Bob", "bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+		Token::String("Hello, Bob!".to_string()),
+	])
+}
+
+#[test]
+fn token_domain_separators() {
+	let dai = H160::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap();
+	assert_eq!(
+		H256::from_str("dbb8cf42e1ecb028be3f3dbc922e1d878b963f411dc388ced501601c60f7c6f7").unwrap(),
+		Domain::new("Dai Stablecoin", "1", 1, dai).separator(),
+	);
+
+	let usdc = H160::from_str("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap();
+	assert_eq!(
+		H256::from_str("06c37168a7db5138defc7866392bb87a741f9b3d104deb5094588ce041cae335").unwrap(),
+		Domain::new("USD Coin", "2", 1, usdc).separator(),
+	);
+}
+
+#[test]
+fn domain_type() {
+	let mut domain = Domain::new("Uniswap", "1", 1, H160::zero());
+	assert_eq!(
+		"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+		domain.encode_type(),
+	);
+
+	// UNI has no version.
+	domain.version = None;
+	assert_eq!("EIP712Domain(string name,uint256 chainId,address verifyingContract)", domain.encode_type());
+
+	assert_eq!("EIP712Domain()", Domain::default().encode_type());
+	assert_eq!(H256(keccak256(&keccak256(b"EIP712Domain()"))), Domain::default().separator());
+}
+
+#[test]
+fn typed_data_digest() {
+	let domain = Domain::new("Dai Stablecoin", "1", 1, H160::zero());
+	let struct_hash = H256::from_low_u64_be(1);
+	let mut data = vec![0x19, 0x01];
+	data.extend_from_slice(domain.separator().as_bytes());
+	data.extend_from_slice(struct_hash.as_bytes());
+	assert_eq!(H256(keccak256(&data)), digest(&domain, &struct_hash));
+}
+
+#[test]
+fn eip_example() {
+	let schema: Schema = serde_json::from_str(MAIL_TYPES).unwrap();
+	assert_eq!(
+		Ok("Mail(Person from,Person to,string contents)Person(string name,address wallet)".to_string()),
+		schema.encode_type("Mail"),
+	);
+	assert_eq!(
+		Ok(H256::from_str("a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2").unwrap()),
+		schema.type_hash("Mail"),
+	);
+	assert_eq!(
+		Ok(H256::from_str("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e").unwrap()),
+		schema.hash_struct("Mail", &mail()),
+	);
+
+	// The domain hashed through the schema matches the domain separator.
+	let domain = Token::Tuple(vec![
+		Token::String("Ether Mail".to_string()),
+		Token::String("1".to_string()),
+		Token::Uint(1.into()),
+		Token::Address(mail_domain().verifying_contract.unwrap()),
+	]);
+	assert_eq!(
+		H256::from_str("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f").unwrap(),
+		mail_domain().separator(),
+	);
+	assert_eq!(Ok(mail_domain().separator()), schema.hash_struct("EIP712Domain", &domain));
+
+	let digest = schema.digest(&mail_domain(), "Mail", &mail()).unwrap();
+	assert_eq!(
+		H256::from_str("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2").unwrap(),
+		digest,
+	);
+
+	// Signed by the key `keccak256("cow")`.
+	let key = SecretKey::from_slice(&keccak256(b"cow")).unwrap();
+	let key = SecretKeyRef::new(&key);
+	assert_eq!(H160::from_str("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826").unwrap(), key.address());
+	let signature = key.sign_message(digest.as_bytes()).unwrap();
+	assert_eq!(1, signature.v);
+	assert_eq!(H256::from_str("4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d").unwrap(), signature.r);
+	assert_eq!(H256::from_str("07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562").unwrap(), signature.s);
+}
+
+#[test]
+fn nested_structs_and_arrays() {
+	let schema = Schema::new()
+		.with_type("Mail", vec![
+			Member::new("from", "Person"),
+			Member::new("to", "Person[]"),
+			Member::new("contents", "string"),
+			Member::new("attachments", "bytes32[2]"),
+		])
+		.with_type("Person", vec![Member::new("name", "string"), Member::new("wallets", "address[]")])
+		.with_type("Group", vec![Member::new("members", "Person[]")]);
+	assert_eq!(
+		Ok("Mail(Person from,Person[] to,string contents,bytes32[2] attachments)Person(string name,address[] wallets)".to_string()),
+		schema.encode_type("Mail"),
+	);
+	assert_eq!(
+		Ok("Group(Person[] members)Person(string name,address[] wallets)".to_string()),
+		schema.encode_type("Group"),
+	);
+
+	let wallets = vec![Token::Address(H160::from_low_u64_be(1)), Token::Address(H160::from_low_u64_be(2))];
+	let bob = Token::Tuple(vec![Token::String("Bob".to_string()), Token::Array(wallets)]);
+	let attachment = |first: u8| {
+		let mut value = vec![0u8; 32];
+		value[0] = first;
+		Token::FixedBytes(value)
+	};
+	let mail = Token::Tuple(vec![
+		bob.clone(),
+		Token::Array(vec![bob.clone(), bob.clone()]),
+		Token::String("Hi".to_string()),
+		Token::FixedArray(vec![attachment(1), attachment(2)]),
+	]);
+
+	// `encodeData` is a word per member, arrays being the hash of their concatenated encoded items.
+	let bob_hash = schema.hash_struct("Person", &bob).unwrap();
+	let mut bobs = bob_hash.as_bytes().to_vec();
+	bobs.extend_from_slice(bob_hash.as_bytes());
+	let mut attachments = vec![0u8; 64];
+	attachments[0] = 1;
+	attachments[32] = 2;
+	let mut expected = bob_hash.as_bytes().to_vec();
+	expected.extend_from_slice(&keccak256(&bobs));
+	expected.extend_from_slice(&keccak256(b"Hi"));
+	expected.extend_from_slice(&keccak256(&attachments));
+	assert_eq!(Ok(expected), schema.encode_data("Mail", &mail));
+
+	let mut expected = keccak256(b"Person(string name,address[] wallets)").to_vec();
+	expected.extend_from_slice(&keccak256(b"Bob"));
+	let mut wallets_encoder: FixedNumberToBytes = Default::default();
+	wallets_encoder.push_h160(&H160::from_low_u64_be(1));
+	wallets_encoder.push_h160(&H160::from_low_u64_be(2));
+	expected.extend_from_slice(&keccak256(&Vec::from(wallets_encoder)));
+	assert_eq!(H256(keccak256(&expected)), bob_hash);
+}
+
+#[test]
+fn invalid_values() {
+	let schema = Schema::new()
+		.with_type("Order", vec![Member::new("amount", "uint256"), Member::new("tokens", "address[2]")]);
+	let tokens = Token::FixedArray(vec![Token::Address(H160::zero()), Token::Address(H160::zero())]);
+	assert!(schema.hash_struct("Order", &Token::Tuple(vec![Token::Uint(U256::one()), tokens.clone()])).is_ok());
+
+	assert_eq!(Err(ERC20Error::UnexpectedType), schema.encode_type("Unknown"));
+	assert_eq!(
+		Err(ERC20Error::UnexpectedType),
+		schema.hash_struct("Order", &Token::Tuple(vec![Token::Bool(true), tokens.clone()])),
+	);
+	assert_eq!(
+		Err(ERC20Error::UnexpectedSize),
+		schema.hash_struct("Order", &Token::Tuple(vec![Token::Uint(U256::one())])),
+	);
+	assert_eq!(
+		Err(ERC20Error::UnexpectedSize),
+		schema.hash_struct("Order", &Token::Tuple(vec![
+			Token::Uint(U256::one()),
+			Token::FixedArray(vec![Token::Address(H160::zero())]),
+		])),
+	);
+	assert_eq!(Err(ERC20Error::UnexpectedType), schema.hash_struct("Order", &Token::Uint(U256::one())));
+
+	// Integers have a size in bits, a multiple of 8 up to 256.
+	for (valid_type, value) in [("uint8", Token::Uint(U256::one())), ("int256", Token::Int(U256::one()))].iter() {
+		let schema = Schema::new().with_type("Value", vec![Member::new("value", valid_type)]);
+		assert!(schema.encode_data("Value", &Token::Tuple(vec![value.clone()])).is_ok(), "{}", valid_type);
+	}
+	for invalid_type in &["uint", "uintfoo", "uint7", "uint264", "uint08", "int", "int7", "int0"] {
+		let value = if invalid_type.starts_with("uint") { Token::Uint(U256::one()) } else { Token::Int(U256::one()) };
+		let schema = Schema::new().with_type("Value", vec![Member::new("value", invalid_type)]);
+		assert_eq!(
+			Err(ERC20Error::UnexpectedType),
+			schema.encode_data("Value", &Token::Tuple(vec![value])),
+			"{}",
+			invalid_type,
+		);
+	}
+}
+
+#[test]
+fn fixed_and_dynamic_bytes() {
+	let schema = Schema::new()
+		.with_type("Data", vec![Member::new("id", "bytes4"), Member::new("payload", "bytes")]);
+	let data = |id: Token, payload: Token| Token::Tuple(vec![id, payload]);
+	let id = Token::FixedBytes(vec![0xa9, 0x05, 0x9c, 0xbb]);
+
+	let mut expected = vec![0u8; 32];
+	expected[..4].copy_from_slice(&[0xa9, 0x05, 0x9c, 0xbb]);
+	expected.extend_from_slice(&keccak256(&[1, 2, 3]));
+	assert_eq!(Ok(expected), schema.encode_data("Data", &data(id.clone(), Token::Bytes(vec![1, 2, 3]))));
+
+	// `bytes` is only hashed, never padded.
+	assert_eq!(
+		Err(ERC20Error::UnexpectedType),
+		schema.encode_data("Data", &data(id.clone(), Token::FixedBytes(vec![1, 2, 3]))),
+	);
+	// `bytesN` values have exactly N bytes.
+	for invalid_id in [vec![], vec![0xa9, 0x05, 0x9c], vec![0u8; 5]].iter().cloned() {
+		assert_eq!(
+			Err(ERC20Error::UnexpectedSize),
+			schema.encode_data("Data", &data(Token::FixedBytes(invalid_id), Token::Bytes(Vec::new()))),
+		);
+	}
+	for invalid_type in &["bytes0", "bytes33", "bytesX"] {
+		let schema = Schema::new().with_type("Data", vec![Member::new("id", invalid_type)]);
+		assert_eq!(
+			Err(ERC20Error::UnexpectedType),
+			schema.encode_data("Data", &Token::Tuple(vec![Token::FixedBytes(vec![1])])),
+		);
+	}
+}
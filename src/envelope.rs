@@ -0,0 +1,195 @@
+//! EIP-2718 typed transaction envelopes.
+
+use crate::ERC20Error;
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::convert::TryFrom;
+use web3::types::{
+	AccessList,
+	Transaction,
+	H160,
+	H256,
+	U256,
+};
+
+/// EIP-2718 transaction type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionType {
+	/// Legacy transaction, with no type or type `0`.
+	Legacy,
+	/// EIP-2930 transaction with an access list, type `1`.
+	AccessList,
+	/// EIP-1559 transaction with dynamic fees, type `2`.
+	DynamicFee,
+}
+
+impl TransactionType {
+	/// Returns the EIP-2718 type byte, `None` for legacy transactions.
+	pub fn type_byte(&self) -> Option<u8> {
+		match self {
+			TransactionType::Legacy => None,
+			TransactionType::AccessList => Some(1),
+			TransactionType::DynamicFee => Some(2),
+		}
+	}
+}
+
+impl TryFrom<u64> for TransactionType {
+	type Error = ERC20Error;
+
+	fn try_from(value: u64) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(TransactionType::Legacy),
+			1 => Ok(TransactionType::AccessList),
+			2 => Ok(TransactionType::DynamicFee),
+			_ => Err(ERC20Error::UnexpectedType),
+		}
+	}
+}
+
+/// How the transaction pays for gas.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GasPricing {
+	/// Fixed gas price, used by legacy and EIP-2930 transactions.
+	Legacy {
+		/// Price paid per gas unit.
+		gas_price: U256,
+	},
+	/// EIP-1559 fees, the base fee is burnt and the priority fee goes to the block producer.
+	DynamicFee {
+		/// Maximum price paid per gas unit, including the base fee.
+		max_fee_per_gas: U256,
+		/// Maximum price paid per gas unit on top of the base fee.
+		max_priority_fee_per_gas: U256,
+	},
+}
+
+impl GasPricing {
+	/// Returns the price paid per gas unit for the block base fee, or `None` if the transaction
+	/// could not be included in such a block.
+	///
+	/// # Arguments
+	///
+	/// * `base_fee` - The block base fee per gas, zero before EIP-1559.
+	///
+	pub fn effective_gas_price(&self, base_fee: U256) -> Option<U256> {
+		match self {
+			GasPricing::Legacy { gas_price } => if *gas_price < base_fee {
+				None
+			} else {
+				Some(*gas_price)
+			},
+			GasPricing::DynamicFee { max_fee_per_gas, max_priority_fee_per_gas } => {
+				if *max_fee_per_gas < base_fee {
+					return None;
+				}
+				let with_priority = base_fee.saturating_add(*max_priority_fee_per_gas);
+				Some(std::cmp::min(*max_fee_per_gas, with_priority))
+			}
+		}
+	}
+
+	/// Returns the price per gas unit received by the block producer.
+	///
+	/// # Arguments
+	///
+	/// * `base_fee` - The block base fee per gas, zero before EIP-1559.
+	///
+	pub fn priority_fee_per_gas(&self, base_fee: U256) -> Option<U256> {
+		Some(self.effective_gas_price(base_fee)? - base_fee)
+	}
+
+	/// Returns the Ether fee paid for the gas used.
+	///
+	/// # Arguments
+	///
+	/// * `gas_used` - The gas used by the transaction, from its receipt.
+	/// * `base_fee` - The block base fee per gas, zero before EIP-1559.
+	///
+	pub fn fee(&self, gas_used: U256, base_fee: U256) -> Option<U256> {
+		self.effective_gas_price(base_fee)?.checked_mul(gas_used)
+	}
+}
+
+/// EIP-2718 information of a transaction: its type, gas pricing, and access list.
+///
+/// ```
+/// use erc20::envelope::{
+///     GasPricing,
+///     TransactionEnvelope,
+///     TransactionType,
+/// };
+/// use std::convert::TryFrom;
+/// use web3::types::Transaction;
+///
+/// let transaction = Transaction {
+///     transaction_type: Some(2.into()),
+///     max_fee_per_gas: Some(100.into()),
+///     max_priority_fee_per_gas: Some(2.into()),
+///     ..Default::default()
+/// };
+/// let envelope = TransactionEnvelope::try_from(&transaction).unwrap();
+/// assert_eq!(TransactionType::DynamicFee, envelope.transaction_type);
+/// assert_eq!(Some(42.into()), envelope.gas_pricing.effective_gas_price(40.into()));
+/// assert_eq!(Some(100.into()), envelope.gas_pricing.effective_gas_price(100.into()));
+/// assert_eq!(None, envelope.gas_pricing.effective_gas_price(101.into()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionEnvelope {
+	/// The transaction type.
+	pub transaction_type: TransactionType,
+	/// The gas pricing.
+	pub gas_pricing: GasPricing,
+	/// The addresses and storage keys pre-warmed by the transaction, empty for legacy transactions.
+	pub access_list: AccessList,
+}
+
+impl TryFrom<&Transaction> for TransactionEnvelope {
+	type Error = ERC20Error;
+
+	fn try_from(transaction: &Transaction) -> Result<Self, Self::Error> {
+		let transaction_type = match transaction.transaction_type {
+			Some(value) => TransactionType::try_from(value.as_u64())?,
+			None => TransactionType::Legacy,
+		};
+		let gas_pricing = match transaction_type {
+			TransactionType::Legacy | TransactionType::AccessList => GasPricing::Legacy {
+				gas_price: transaction.gas_price.ok_or(ERC20Error::UnexpectedType)?,
+			},
+			TransactionType::DynamicFee => GasPricing::DynamicFee {
+				max_fee_per_gas: transaction.max_fee_per_gas.ok_or(ERC20Error::UnexpectedType)?,
+				max_priority_fee_per_gas: transaction.max_priority_fee_per_gas.ok_or(ERC20Error::UnexpectedType)?,
+			},
+		};
+		let access_list = match transaction_type {
+			TransactionType::Legacy => Vec::new(),
+			_ => transaction.access_list.clone().unwrap_or_default(),
+		};
+		Ok(Self {
+			transaction_type,
+			gas_pricing,
+			access_list,
+		})
+	}
+}
+
+impl TransactionEnvelope {
+	/// Returns the storage keys of `address` pre-warmed by the access list,
+	/// e.g. the balance slots of a token contract.
+	///
+	/// # Arguments
+	///
+	/// * `address` - The contract address.
+	///
+	pub fn storage_keys(&self, address: &H160) -> Vec<H256> {
+		self.access_list.iter()
+			.filter(|it| it.address == *address)
+			.flat_map(|it| it.storage_keys.iter().cloned())
+			.collect()
+	}
+}
@@ -0,0 +1,131 @@
+use crate::{
+	envelope::{
+		GasPricing,
+		TransactionEnvelope,
+		TransactionType,
+	},
+	erc20::ERC20Call,
+	receipt::TransactionWithReceipt,
+	transaction::{
+		ParsedTransaction,
+		TransactionAndTransferType,
+	},
+	ERC20Error,
+};
+use std::convert::{
+	TryFrom,
+	TryInto,
+};
+use web3::types::{
+	AccessListItem,
+	Transaction,
+	TransactionReceipt,
+	H160,
+	H256,
+	U256,
+};
+
+const TOKEN: u64 = 0x70c3e;
+
+fn dynamic_fee_transfer() -> Transaction {
+	Transaction {
+		from: Some(H160::random()),
+		to: Some(H160::from_low_u64_be(TOKEN)),
+		input: ERC20Call::Transfer { to: H160::random(), value: 10.into() }.encode(),
+		transaction_type: Some(2.into()),
+		max_fee_per_gas: Some(100.into()),
+		max_priority_fee_per_gas: Some(3.into()),
+		access_list: Some(vec![
+			AccessListItem {
+				address: H160::from_low_u64_be(TOKEN),
+				storage_keys: vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)],
+			},
+			AccessListItem {
+				address: H160::random(),
+				storage_keys: vec![H256::from_low_u64_be(3)],
+			},
+		]),
+		..Default::default()
+	}
+}
+
+#[test]
+fn legacy_envelope() {
+	let transaction = Transaction {
+		gas_price: Some(20.into()),
+		..Default::default()
+	};
+	let envelope = TransactionEnvelope::try_from(&transaction).unwrap();
+	assert_eq!(TransactionType::Legacy, envelope.transaction_type);
+	assert_eq!(None, envelope.transaction_type.type_byte());
+	assert_eq!(GasPricing::Legacy { gas_price: 20.into() }, envelope.gas_pricing);
+	assert!(envelope.access_list.is_empty());
+
+	assert_eq!(Some(U256::from(20)), envelope.gas_pricing.effective_gas_price(U256::zero()));
+	assert_eq!(Some(U256::from(5)), envelope.gas_pricing.priority_fee_per_gas(15.into()));
+	assert_eq!(None, envelope.gas_pricing.effective_gas_price(21.into()));
+}
+
+#[test]
+fn access_list_envelope() {
+	let mut transaction = dynamic_fee_transfer();
+	transaction.transaction_type = Some(1.into());
+	transaction.gas_price = Some(20.into());
+	let envelope = TransactionEnvelope::try_from(&transaction).unwrap();
+	assert_eq!(TransactionType::AccessList, envelope.transaction_type);
+	assert_eq!(Some(1), envelope.transaction_type.type_byte());
+	assert_eq!(GasPricing::Legacy { gas_price: 20.into() }, envelope.gas_pricing);
+	assert_eq!(2, envelope.access_list.len());
+}
+
+#[test]
+fn dynamic_fee_envelope() {
+	let parsed: ParsedTransaction = dynamic_fee_transfer().into();
+	let envelope = parsed.envelope().unwrap();
+	assert_eq!(TransactionType::DynamicFee, envelope.transaction_type);
+	assert_eq!(GasPricing::DynamicFee {
+		max_fee_per_gas: 100.into(),
+		max_priority_fee_per_gas: 3.into(),
+	}, envelope.gas_pricing);
+
+	assert_eq!(Some(U256::from(53)), envelope.gas_pricing.effective_gas_price(50.into()));
+	assert_eq!(Some(U256::from(3)), envelope.gas_pricing.priority_fee_per_gas(50.into()));
+	// The priority fee is capped by the max fee.
+	assert_eq!(Some(U256::from(100)), envelope.gas_pricing.effective_gas_price(99.into()));
+	assert_eq!(Some(U256::from(1)), envelope.gas_pricing.priority_fee_per_gas(99.into()));
+	assert_eq!(Some(U256::from(53_000)), envelope.gas_pricing.fee(1_000.into(), 50.into()));
+}
+
+#[test]
+fn invalid_envelopes() {
+	let mut transaction = dynamic_fee_transfer();
+	transaction.max_fee_per_gas = None;
+	assert_eq!(Err(ERC20Error::UnexpectedType), TransactionEnvelope::try_from(&transaction));
+
+	transaction.transaction_type = Some(3.into());
+	assert_eq!(Err(ERC20Error::UnexpectedType), TransactionEnvelope::try_from(&transaction));
+}
+
+#[test]
+fn transfer_access_list() {
+	let transfer: TransactionAndTransferType = dynamic_fee_transfer().try_into().unwrap();
+	assert_eq!(
+		Ok(vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)]),
+		transfer.contract_storage_keys(),
+	);
+}
+
+#[test]
+fn transfer_effective_fee() {
+	let receipt = TransactionReceipt {
+		gas_used: Some(50_000.into()),
+		..Default::default()
+	};
+	let with_receipt = TransactionWithReceipt::new(dynamic_fee_transfer(), receipt).unwrap();
+	assert_eq!(Ok(U256::from(50_000 * 53)), with_receipt.effective_fee(50.into()));
+	assert_eq!(Err(ERC20Error::BaseFeeAboveMaxFee), with_receipt.effective_fee(101.into()));
+
+	let with_receipt = TransactionWithReceipt::new(dynamic_fee_transfer(), Default::default()).unwrap();
+	assert_eq!(None, with_receipt.fee());
+	assert_eq!(Err(ERC20Error::UnknownGasUsed), with_receipt.effective_fee(50.into()));
+}
@@ -1,7 +1,15 @@
-///! ERC20 specific information.
+//! ERC20 specific information.
 
-use crate::ERC20Error;
+use crate::{
+	chain,
+	util::{
+		BytesToFixedNumber,
+		FixedNumberToBytes,
+	},
+	ERC20Error,
+};
 use maplit::hashmap;
+use once_cell::sync::Lazy;
 use serde::{
 	Deserialize,
 	Serialize,
@@ -14,7 +22,12 @@ use std::{
 	},
 	str::FromStr,
 };
-use web3::types::H160;
+use web3::types::{
+	Bytes,
+	H160,
+	H256,
+	U256,
+};
 
 /// ERC20 method operation
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -26,6 +39,30 @@ pub enum ERC20Method {
 	Approve,
 	/// Returns the account balance of another account with address `owner`.
 	BalanceOf,
+	/// Destroys `value` tokens of the caller, reducing the total supply.
+	Burn,
+	/// Destroys `value` tokens of `from`, deducted from the caller's allowance.
+	BurnFrom,
+	/// DAI's pre EIP-2612 permit, allowing `spender` to withdraw an unlimited amount, or nothing, from `holder` with a signature of the holder.
+	DaiPermit,
+	/// Returns the number of decimals the token uses, optional.
+	Decimals,
+	/// Decreases the allowance of `spender` by `value`, without the race condition of `approve`.
+	DecreaseAllowance,
+	/// Returns the EIP-712 domain separator signed by permits.
+	DomainSeparator,
+	/// Increases the allowance of `spender` by `value`, without the race condition of `approve`.
+	IncreaseAllowance,
+	/// Creates `value` tokens assigned to `to`, increasing the total supply.
+	Mint,
+	/// Returns the name of the token, optional.
+	Name,
+	/// Returns the current permit nonce of `owner`.
+	Nonces,
+	/// EIP-2612 permit, allowing `spender` to withdraw up to `value` from `owner` with a signature of the owner, until `deadline`.
+	Permit,
+	/// Returns the symbol of the token, optional.
+	Symbol,
 	/// Returns the total token supply.
 	TotalSupply,
 	/// Transfers `value` amount of tokens to address `to`, and MUST fire the Transfer event. The function SHOULD throw if the message caller’s account balance does not have enough tokens to spend.
@@ -36,6 +73,33 @@ pub enum ERC20Method {
 	Unidentified,
 }
 
+impl ERC20Method {
+	/// Returns the canonical signature, whose hash starts with the selector, `None` if unidentified.
+	pub fn signature(&self) -> Option<&'static str> {
+		match self {
+			ERC20Method::Allowance => Some("allowance(address,address)"),
+			ERC20Method::Approve => Some("approve(address,uint256)"),
+			ERC20Method::BalanceOf => Some("balanceOf(address)"),
+			ERC20Method::Burn => Some("burn(uint256)"),
+			ERC20Method::BurnFrom => Some("burnFrom(address,uint256)"),
+			ERC20Method::DaiPermit => Some("permit(address,address,uint256,uint256,bool,uint8,bytes32,bytes32)"),
+			ERC20Method::Decimals => Some("decimals()"),
+			ERC20Method::DecreaseAllowance => Some("decreaseAllowance(address,uint256)"),
+			ERC20Method::DomainSeparator => Some("DOMAIN_SEPARATOR()"),
+			ERC20Method::IncreaseAllowance => Some("increaseAllowance(address,uint256)"),
+			ERC20Method::Mint => Some("mint(address,uint256)"),
+			ERC20Method::Name => Some("name()"),
+			ERC20Method::Nonces => Some("nonces(address)"),
+			ERC20Method::Permit => Some("permit(address,address,uint256,uint256,uint8,bytes32,bytes32)"),
+			ERC20Method::Symbol => Some("symbol()"),
+			ERC20Method::TotalSupply => Some("totalSupply()"),
+			ERC20Method::Transfer => Some("transfer(address,uint256)"),
+			ERC20Method::TransferFrom => Some("transferFrom(address,address,uint256)"),
+			ERC20Method::Unidentified => None,
+		}
+	}
+}
+
 impl TryFrom<ERC20Method> for [u8; 4] {
 	type Error = ERC20Error;
 
@@ -44,6 +108,18 @@ impl TryFrom<ERC20Method> for [u8; 4] {
 			ERC20Method::Allowance => Ok([0xdd, 0x62, 0xed, 0x3e]),
 			ERC20Method::Approve => Ok([0x09, 0x5e, 0xa7, 0xb3]),
 			ERC20Method::BalanceOf => Ok([0x70, 0xa0, 0x82, 0x31]),
+			ERC20Method::Burn => Ok([0x42, 0x96, 0x6c, 0x68]),
+			ERC20Method::BurnFrom => Ok([0x79, 0xcc, 0x67, 0x90]),
+			ERC20Method::DaiPermit => Ok([0x8f, 0xcb, 0xaf, 0x0c]),
+			ERC20Method::Decimals => Ok([0x31, 0x3c, 0xe5, 0x67]),
+			ERC20Method::DecreaseAllowance => Ok([0xa4, 0x57, 0xc2, 0xd7]),
+			ERC20Method::DomainSeparator => Ok([0x36, 0x44, 0xe5, 0x15]),
+			ERC20Method::IncreaseAllowance => Ok([0x39, 0x50, 0x93, 0x51]),
+			ERC20Method::Mint => Ok([0x40, 0xc1, 0x0f, 0x19]),
+			ERC20Method::Name => Ok([0x06, 0xfd, 0xde, 0x03]),
+			ERC20Method::Nonces => Ok([0x7e, 0xce, 0xbe, 0x00]),
+			ERC20Method::Permit => Ok([0xd5, 0x05, 0xac, 0xcf]),
+			ERC20Method::Symbol => Ok([0x95, 0xd8, 0x9b, 0x41]),
 			ERC20Method::TotalSupply => Ok([0x18, 0x16, 0x0d, 0xdd]),
 			ERC20Method::Transfer => Ok([0xa9, 0x05, 0x9c, 0xbb]),
 			ERC20Method::TransferFrom => Ok([0x23, 0xb8, 0x72, 0xdd]),
@@ -71,6 +147,18 @@ impl From<Vec<u8>> for ERC20Method {
 				Self::Allowance => Self::Allowance.try_into().unwrap(),
 				Self::Approve => Self::Approve.try_into().unwrap(),
 				Self::BalanceOf => Self::BalanceOf.try_into().unwrap(),
+				Self::Burn => Self::Burn.try_into().unwrap(),
+				Self::BurnFrom => Self::BurnFrom.try_into().unwrap(),
+				Self::DaiPermit => Self::DaiPermit.try_into().unwrap(),
+				Self::Decimals => Self::Decimals.try_into().unwrap(),
+				Self::DecreaseAllowance => Self::DecreaseAllowance.try_into().unwrap(),
+				Self::DomainSeparator => Self::DomainSeparator.try_into().unwrap(),
+				Self::IncreaseAllowance => Self::IncreaseAllowance.try_into().unwrap(),
+				Self::Mint => Self::Mint.try_into().unwrap(),
+				Self::Name => Self::Name.try_into().unwrap(),
+				Self::Nonces => Self::Nonces.try_into().unwrap(),
+				Self::Permit => Self::Permit.try_into().unwrap(),
+				Self::Symbol => Self::Symbol.try_into().unwrap(),
 				Self::TotalSupply => Self::TotalSupply.try_into().unwrap(),
 				Self::Transfer => Self::Transfer.try_into().unwrap(),
 				Self::TransferFrom => Self::TransferFrom.try_into().unwrap(),
@@ -85,40 +173,442 @@ impl From<Vec<u8>> for ERC20Method {
 	}
 }
 
-/// Known ERC20 contract addresses.
+/// ERC20 method invocation with its arguments.
+///
+/// ```
+/// use erc20::erc20::ERC20Call;
+/// use web3::types::H160;
+///
+/// let call = ERC20Call::Transfer {
+///     to: H160::from_low_u64_be(1),
+///     value: 2.into(),
+/// };
+/// let calldata = call.encode();
+/// assert_eq!(
+///     "a9059cbb00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002",
+///     hex::encode(&calldata.0),
+/// );
+/// assert_eq!(Ok(call), ERC20Call::decode(&calldata.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ERC20Call {
+	/// `allowance(address,address)`.
+	Allowance {
+		/// The account holding the tokens.
+		owner: H160,
+		/// The account allowed to withdraw them.
+		spender: H160,
+	},
+	/// `approve(address,uint256)`.
+	Approve {
+		/// The account allowed to withdraw the tokens.
+		spender: H160,
+		/// The amount allowed.
+		value: U256,
+	},
+	/// `balanceOf(address)`.
+	BalanceOf {
+		/// The account holding the tokens.
+		owner: H160,
+	},
+	/// `burn(uint256)`.
+	Burn {
+		/// The amount destroyed.
+		value: U256,
+	},
+	/// `burnFrom(address,uint256)`.
+	BurnFrom {
+		/// The account holding the tokens.
+		from: H160,
+		/// The amount destroyed.
+		value: U256,
+	},
+	/// `permit(address,address,uint256,uint256,bool,uint8,bytes32,bytes32)` of DAI.
+	DaiPermit {
+		/// The account holding the tokens.
+		holder: H160,
+		/// The account allowed to withdraw them.
+		spender: H160,
+		/// The holder nonce the signature is valid for.
+		nonce: U256,
+		/// The timestamp after which the signature expires, zero if it never expires.
+		expiry: U256,
+		/// Whether the allowance is unlimited or revoked.
+		allowed: bool,
+		/// The signature recovery id.
+		v: u8,
+		/// The signature `r`.
+		r: H256,
+		/// The signature `s`.
+		s: H256,
+	},
+	/// `decimals()`.
+	Decimals,
+	/// `decreaseAllowance(address,uint256)`.
+	DecreaseAllowance {
+		/// The account allowed to withdraw the tokens.
+		spender: H160,
+		/// The amount subtracted from the allowance.
+		value: U256,
+	},
+	/// `DOMAIN_SEPARATOR()`.
+	DomainSeparator,
+	/// `increaseAllowance(address,uint256)`.
+	IncreaseAllowance {
+		/// The account allowed to withdraw the tokens.
+		spender: H160,
+		/// The amount added to the allowance.
+		value: U256,
+	},
+	/// `mint(address,uint256)`.
+	Mint {
+		/// The recipient.
+		to: H160,
+		/// The amount created.
+		value: U256,
+	},
+	/// `name()`.
+	Name,
+	/// `nonces(address)`.
+	Nonces {
+		/// The account signing permits.
+		owner: H160,
+	},
+	/// `permit(address,address,uint256,uint256,uint8,bytes32,bytes32)`.
+	Permit {
+		/// The account holding the tokens.
+		owner: H160,
+		/// The account allowed to withdraw them.
+		spender: H160,
+		/// The amount allowed.
+		value: U256,
+		/// The timestamp after which the signature expires.
+		deadline: U256,
+		/// The signature recovery id.
+		v: u8,
+		/// The signature `r`.
+		r: H256,
+		/// The signature `s`.
+		s: H256,
+	},
+	/// `symbol()`.
+	Symbol,
+	/// `totalSupply()`.
+	TotalSupply,
+	/// `transfer(address,uint256)`.
+	Transfer {
+		/// The recipient.
+		to: H160,
+		/// The amount transferred.
+		value: U256,
+	},
+	/// `transferFrom(address,address,uint256)`.
+	TransferFrom {
+		/// The sender.
+		from: H160,
+		/// The recipient.
+		to: H160,
+		/// The amount transferred.
+		value: U256,
+	},
+}
+
+impl ERC20Call {
+	/// Returns the method invoked.
+	pub fn method(&self) -> ERC20Method {
+		match self {
+			ERC20Call::Allowance { .. } => ERC20Method::Allowance,
+			ERC20Call::Approve { .. } => ERC20Method::Approve,
+			ERC20Call::BalanceOf { .. } => ERC20Method::BalanceOf,
+			ERC20Call::Burn { .. } => ERC20Method::Burn,
+			ERC20Call::BurnFrom { .. } => ERC20Method::BurnFrom,
+			ERC20Call::DaiPermit { .. } => ERC20Method::DaiPermit,
+			ERC20Call::Decimals => ERC20Method::Decimals,
+			ERC20Call::DecreaseAllowance { .. } => ERC20Method::DecreaseAllowance,
+			ERC20Call::DomainSeparator => ERC20Method::DomainSeparator,
+			ERC20Call::IncreaseAllowance { .. } => ERC20Method::IncreaseAllowance,
+			ERC20Call::Mint { .. } => ERC20Method::Mint,
+			ERC20Call::Name => ERC20Method::Name,
+			ERC20Call::Nonces { .. } => ERC20Method::Nonces,
+			ERC20Call::Permit { .. } => ERC20Method::Permit,
+			ERC20Call::Symbol => ERC20Method::Symbol,
+			ERC20Call::TotalSupply => ERC20Method::TotalSupply,
+			ERC20Call::Transfer { .. } => ERC20Method::Transfer,
+			ERC20Call::TransferFrom { .. } => ERC20Method::TransferFrom,
+		}
+	}
+
+	/// Encodes the invocation as a transaction input.
+	pub fn encode(&self) -> Bytes {
+		let mut encoder: FixedNumberToBytes = Default::default();
+		let selector: [u8; 4] = self.method().try_into()
+			.expect("Every ERC20Call has a known method");
+		encoder.push_vec(&selector);
+		match self {
+			ERC20Call::Allowance { owner, spender } => {
+				encoder.push_h160(owner);
+				encoder.push_h160(spender);
+			}
+			ERC20Call::Approve { spender, value } => {
+				encoder.push_h160(spender);
+				encoder.push_u256(value);
+			}
+			ERC20Call::BalanceOf { owner } => encoder.push_h160(owner),
+			ERC20Call::Burn { value } => encoder.push_u256(value),
+			ERC20Call::BurnFrom { from, value } => {
+				encoder.push_h160(from);
+				encoder.push_u256(value);
+			}
+			ERC20Call::DaiPermit { holder, spender, nonce, expiry, allowed, v, r, s } => {
+				encoder.push_h160(holder);
+				encoder.push_h160(spender);
+				encoder.push_u256(nonce);
+				encoder.push_u256(expiry);
+				encoder.push_bool(*allowed);
+				encoder.push_usize(*v as usize);
+				encoder.push_h256(r);
+				encoder.push_h256(s);
+			}
+			ERC20Call::Decimals => {}
+			ERC20Call::DecreaseAllowance { spender, value } | ERC20Call::IncreaseAllowance { spender, value } => {
+				encoder.push_h160(spender);
+				encoder.push_u256(value);
+			}
+			ERC20Call::DomainSeparator => {}
+			ERC20Call::Mint { to, value } => {
+				encoder.push_h160(to);
+				encoder.push_u256(value);
+			}
+			ERC20Call::Name => {}
+			ERC20Call::Nonces { owner } => encoder.push_h160(owner),
+			ERC20Call::Permit { owner, spender, value, deadline, v, r, s } => {
+				encoder.push_h160(owner);
+				encoder.push_h160(spender);
+				encoder.push_u256(value);
+				encoder.push_u256(deadline);
+				encoder.push_usize(*v as usize);
+				encoder.push_h256(r);
+				encoder.push_h256(s);
+			}
+			ERC20Call::Symbol => {}
+			ERC20Call::TotalSupply => {}
+			ERC20Call::Transfer { to, value } => {
+				encoder.push_h160(to);
+				encoder.push_u256(value);
+			}
+			ERC20Call::TransferFrom { from, to, value } => {
+				encoder.push_h160(from);
+				encoder.push_h160(to);
+				encoder.push_u256(value);
+			}
+		}
+		Bytes(encoder.into())
+	}
+
+	/// Decodes the invocation from a transaction input.
+	///
+	/// # Arguments
+	///
+	/// * `data` - The transaction input, starting with the method selector.
+	///
+	pub fn decode(data: &[u8]) -> Result<Self, ERC20Error> {
+		let method: ERC20Method = data.to_vec().into();
+		if method == ERC20Method::Unidentified {
+			return Err(ERC20Error::UnexpectedType);
+		}
+		let mut decoder: BytesToFixedNumber = data[4..].to_vec().into();
+		match method {
+			ERC20Method::Allowance => Ok(ERC20Call::Allowance {
+				owner: decoder.next_h160()?,
+				spender: decoder.next_h160()?,
+			}),
+			ERC20Method::Approve => Ok(ERC20Call::Approve {
+				spender: decoder.next_h160()?,
+				value: decoder.next_u256()?,
+			}),
+			ERC20Method::BalanceOf => Ok(ERC20Call::BalanceOf {
+				owner: decoder.next_h160()?,
+			}),
+			ERC20Method::Burn => Ok(ERC20Call::Burn {
+				value: decoder.next_u256()?,
+			}),
+			ERC20Method::BurnFrom => Ok(ERC20Call::BurnFrom {
+				from: decoder.next_h160()?,
+				value: decoder.next_u256()?,
+			}),
+			ERC20Method::DaiPermit => Ok(ERC20Call::DaiPermit {
+				holder: decoder.next_h160()?,
+				spender: decoder.next_h160()?,
+				nonce: decoder.next_u256()?,
+				expiry: decoder.next_u256()?,
+				allowed: decoder.next_bool()?,
+				v: decoder.next_u8()?,
+				r: decoder.next_h256()?,
+				s: decoder.next_h256()?,
+			}),
+			ERC20Method::Decimals => Ok(ERC20Call::Decimals),
+			ERC20Method::DecreaseAllowance => Ok(ERC20Call::DecreaseAllowance {
+				spender: decoder.next_h160()?,
+				value: decoder.next_u256()?,
+			}),
+			ERC20Method::DomainSeparator => Ok(ERC20Call::DomainSeparator),
+			ERC20Method::IncreaseAllowance => Ok(ERC20Call::IncreaseAllowance {
+				spender: decoder.next_h160()?,
+				value: decoder.next_u256()?,
+			}),
+			ERC20Method::Mint => Ok(ERC20Call::Mint {
+				to: decoder.next_h160()?,
+				value: decoder.next_u256()?,
+			}),
+			ERC20Method::Name => Ok(ERC20Call::Name),
+			ERC20Method::Nonces => Ok(ERC20Call::Nonces {
+				owner: decoder.next_h160()?,
+			}),
+			ERC20Method::Permit => Ok(ERC20Call::Permit {
+				owner: decoder.next_h160()?,
+				spender: decoder.next_h160()?,
+				value: decoder.next_u256()?,
+				deadline: decoder.next_u256()?,
+				v: decoder.next_u8()?,
+				r: decoder.next_h256()?,
+				s: decoder.next_h256()?,
+			}),
+			ERC20Method::Symbol => Ok(ERC20Call::Symbol),
+			ERC20Method::TotalSupply => Ok(ERC20Call::TotalSupply),
+			ERC20Method::Transfer => Ok(ERC20Call::Transfer {
+				to: decoder.next_h160()?,
+				value: decoder.next_u256()?,
+			}),
+			ERC20Method::TransferFrom => Ok(ERC20Call::TransferFrom {
+				from: decoder.next_h160()?,
+				to: decoder.next_h160()?,
+				value: decoder.next_u256()?,
+			}),
+			ERC20Method::Unidentified => Err(ERC20Error::UnexpectedType),
+		}
+	}
+
+	/// Returns the approval signed off-chain by the owner for a `permit` invocation.
+	pub fn permit_approval(&self) -> Result<PermitApproval, ERC20Error> {
+		match self {
+			ERC20Call::Permit { owner, spender, value, deadline, .. } => Ok(PermitApproval {
+				approval: Approval {
+					owner: *owner,
+					spender: *spender,
+					value: *value,
+				},
+				nonce: None,
+				deadline: Some(*deadline),
+			}),
+			ERC20Call::DaiPermit { holder, spender, nonce, expiry, allowed, .. } => Ok(PermitApproval {
+				approval: Approval {
+					owner: *holder,
+					spender: *spender,
+					value: if *allowed { U256::MAX } else { U256::zero() },
+				},
+				nonce: Some(*nonce),
+				deadline: if expiry.is_zero() { None } else { Some(*expiry) },
+			}),
+			_ => Err(ERC20Error::NoApprovalTransaction),
+		}
+	}
+}
+
+/// Allowance approval, from an `approve` invocation or an `Approval` event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Approval {
+	/// The account holding the tokens.
+	pub owner: H160,
+	/// The account allowed to withdraw them.
+	pub spender: H160,
+	/// The amount allowed.
+	pub value: U256,
+}
+
+/// Allowance approval signed off-chain by the owner, from an EIP-2612 `permit` invocation.
+///
+/// ```
+/// use erc20::erc20::{
+///     ERC20Call,
+///     PermitApproval,
+/// };
+/// use web3::types::{
+///     H160,
+///     H256,
+///     U256,
+/// };
+///
+/// let call = ERC20Call::DaiPermit {
+///     holder: H160::from_low_u64_be(1),
+///     spender: H160::from_low_u64_be(2),
+///     nonce: 0.into(),
+///     expiry: 0.into(),
+///     allowed: true,
+///     v: 27,
+///     r: H256::zero(),
+///     s: H256::zero(),
+/// };
+/// let permit = call.permit_approval().unwrap();
+/// assert_eq!(U256::MAX, permit.approval.value);
+/// assert_eq!(Some(0.into()), permit.nonce);
+/// assert_eq!(None, permit.deadline);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermitApproval {
+	/// The approval, DAI permits allowing either an unlimited amount or nothing.
+	pub approval: Approval,
+	/// The owner nonce the signature is valid for, only part of DAI permits.
+	pub nonce: Option<U256>,
+	/// The timestamp after which the signature expires, `None` if it never expires.
+	pub deadline: Option<U256>,
+}
+
+/// Known ERC20 contract addresses, on Ethereum mainnet and the other networks of `chain`.
 ///
 /// ```
-/// use crate::erc20::erc20::ContractAddress;
+/// use crate::erc20::{
+///     chain,
+///     erc20::ContractAddress,
+/// };
 /// use std::str::FromStr;
 /// use web3::types::H160;
 ///
 /// let tusd_address = H160::from_str("0000000000085d4780B73119b644AE5ecd22b376").unwrap();
 /// assert_eq!("0x0000000000085d4780b73119b644ae5ecd22b376".to_string(), format!("{:?}", tusd_address));
 ///
-/// // Getting the `ContractAddress` from the `H160`.
-/// let contract_address: ContractAddress = tusd_address.into();
+/// // Getting the `ContractAddress` from the `H160` of a mainnet address.
+/// let contract_address: ContractAddress = (chain::MAINNET, tusd_address).into();
 /// assert_eq!(ContractAddress::TUSD, contract_address);
 ///
-/// // Getting the address H160 from the `ContractAddress`.
-/// let usdc_address: web3::types::H160 = crate::erc20::erc20::ContractAddress::USDC.into();
+/// // Getting the address H160 from the `ContractAddress`, on mainnet.
+/// let usdc_address = ContractAddress::USDC.address(chain::MAINNET).unwrap();
 /// assert_eq!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", format!("{:?}", usdc_address));
+///
+/// // USDC has another address on Polygon.
+/// let polygon_usdc = ContractAddress::USDC.address(chain::POLYGON).unwrap();
+/// assert_eq!("0x3c499c542cef5e3811e1192ce70d8cc03d5c3359", format!("{:?}", polygon_usdc));
+/// assert_eq!(ContractAddress::USDC, ContractAddress::from((chain::POLYGON, polygon_usdc)));
+/// assert_eq!(ContractAddress::Unidentified(polygon_usdc), ContractAddress::from((chain::MAINNET, polygon_usdc)));
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ContractAddress {
 	/// Basic Attention Token
 	BAT,
-	/// Binance Token
+	/// Binance Token, the native asset of BSC rather than a token there
 	BNB,
 	/// Binance USD
 	BUSD,
 	/// ChainLink
 	LINK,
-	/// Tether USD
+	/// TrueUSD
 	TUSD,
 	/// USD Coin
 	USDC,
-	/// True USD
+	/// Tether USD
 	USDT,
 	/// Wrapped BTC
 	WBTC,
@@ -143,56 +633,202 @@ pub enum ContractAddress {
 	Unidentified(H160),
 }
 
+/// The address and decimals of the known contracts on each chain.
+static CONTRACT_ADDRESSES: Lazy<HashMap<(u64, ContractAddress), (H160, u8)>> = Lazy::new(|| hashmap! {
+	(chain::MAINNET, ContractAddress::TUSD) => (H160::from_str("0000000000085d4780B73119b644AE5ecd22b376").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::LINK) => (H160::from_str("514910771af9ca656af840dff83e8264ecf986ca").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::BNB) => (H160::from_str("B8c77482e45F1F44dE1745F52C74426C631bDD52").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::USDC) => (H160::from_str("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(), 6),
+	(chain::MAINNET, ContractAddress::WBTC) => (H160::from_str("2260fac5e5542a773aa44fbcfedf7c193bc2c599").unwrap(), 8),
+	(chain::MAINNET, ContractAddress::cDAI) => (H160::from_str("5d3a536E4D6DbD6114cc1Ead35777bAB948E3643").unwrap(), 8),
+	(chain::MAINNET, ContractAddress::OKB) => (H160::from_str("75231f58b43240c9718dd58b4967c5114342a86c").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::CRO) => (H160::from_str("a0b73e1ff0b80914ab6fe0444e65848c4c34450b").unwrap(), 8),
+	(chain::MAINNET, ContractAddress::WFIL) => (H160::from_str("6e1A19F235bE7ED8E3369eF73b196C07257494DE").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::BAT) => (H160::from_str("0d8775f648430679a709e98d2b0cb6250d2887ef").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::BUSD) => (H160::from_str("4fabb145d64652a948d72533023f6e7a623c7c53").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::USDT) => (H160::from_str("dac17f958d2ee523a2206206994597c13d831ec7").unwrap(), 6),
+	(chain::MAINNET, ContractAddress::LEO) => (H160::from_str("2af5d2ad76741191d15dfe7bf6ac92d4bd912ca3").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::VEN) => (H160::from_str("d850942ef8811f2a866692a623011bde52a462c1").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::DAI) => (H160::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap(), 18),
+	(chain::MAINNET, ContractAddress::UNI) => (H160::from_str("1f9840a85d5af5bf1d1762f925bdaddc4201f984").unwrap(), 18),
+	(chain::OPTIMISM, ContractAddress::USDC) => (H160::from_str("0b2C639c533813f4Aa9D7837CAf62653d097Ff85").unwrap(), 6),
+	(chain::OPTIMISM, ContractAddress::USDT) => (H160::from_str("94b008aA00579c1307B0EF2c499aD98a8ce58e58").unwrap(), 6),
+	(chain::OPTIMISM, ContractAddress::DAI) => (H160::from_str("DA10009cBd5D07dd0CeCc66161FC93D7c9000da1").unwrap(), 18),
+	(chain::OPTIMISM, ContractAddress::WBTC) => (H160::from_str("68f180fcCe6836688e9084f035309E29Bf0A2095").unwrap(), 8),
+	(chain::OPTIMISM, ContractAddress::LINK) => (H160::from_str("350a791Bfc2C21F9Ed5d10980Dad2e2638ffa7f6").unwrap(), 18),
+	(chain::OPTIMISM, ContractAddress::UNI) => (H160::from_str("6fd9d7AD17242c41f7131d257212c54A0e816691").unwrap(), 18),
+	(chain::BSC, ContractAddress::USDC) => (H160::from_str("8AC76a51cc950d9822D68b83fE1Ad97B32Cd580d").unwrap(), 18),
+	(chain::BSC, ContractAddress::USDT) => (H160::from_str("55d398326f99059fF775485246999027B3197955").unwrap(), 18),
+	(chain::BSC, ContractAddress::DAI) => (H160::from_str("1AF3F329e8BE154074D8769D1FFa4eE058B1DBc3").unwrap(), 18),
+	(chain::BSC, ContractAddress::BUSD) => (H160::from_str("e9e7CEA3DedcA5984780Bafc599bD69ADd087D56").unwrap(), 18),
+	(chain::BSC, ContractAddress::LINK) => (H160::from_str("F8A0BF9cF54Bb92F17374d9e9A321E6a111a51bD").unwrap(), 18),
+	(chain::BSC, ContractAddress::UNI) => (H160::from_str("BF5140A22578168FD562DCcF235E5D43A02ce9B1").unwrap(), 18),
+	(chain::POLYGON, ContractAddress::USDC) => (H160::from_str("3c499c542cEF5E3811e1192ce70d8cC03d5c3359").unwrap(), 6),
+	(chain::POLYGON, ContractAddress::USDT) => (H160::from_str("c2132D05D31c914a87C6611C10748AEb04B58e8F").unwrap(), 6),
+	(chain::POLYGON, ContractAddress::DAI) => (H160::from_str("8f3Cf7ad23Cd3CaDbD9735AFf958023239c6A063").unwrap(), 18),
+	(chain::POLYGON, ContractAddress::WBTC) => (H160::from_str("1BFD67037B42Cf73acF2047067bd4F2C47D9BfD6").unwrap(), 8),
+	(chain::POLYGON, ContractAddress::LINK) => (H160::from_str("b0897686c545045aFc77CF20eC7A532E3120E0F1").unwrap(), 18),
+	(chain::POLYGON, ContractAddress::UNI) => (H160::from_str("b33EaAd8d922B1083446DC23f610c2567fB5180f").unwrap(), 18),
+	(chain::ARBITRUM, ContractAddress::USDC) => (H160::from_str("af88d065e77c8cC2239327C5EDb3A432268e5831").unwrap(), 6),
+	(chain::ARBITRUM, ContractAddress::USDT) => (H160::from_str("Fd086bC7CD5C481DCC9C85ebE478A1C0b69FCbb9").unwrap(), 6),
+	(chain::ARBITRUM, ContractAddress::DAI) => (H160::from_str("DA10009cBd5D07dd0CeCc66161FC93D7c9000da1").unwrap(), 18),
+	(chain::ARBITRUM, ContractAddress::WBTC) => (H160::from_str("2f2a2543B76A4166549F7aaB2e75Bef0aefC5B0f").unwrap(), 8),
+	(chain::ARBITRUM, ContractAddress::LINK) => (H160::from_str("f97f4df75117a78c1A5a0DBb814Af92458539FB4").unwrap(), 18),
+	(chain::ARBITRUM, ContractAddress::UNI) => (H160::from_str("Fa7F8980b0f1E64A2062791cc3b0871572f1F7f0").unwrap(), 18),
+});
+
+static ADDRESS_CONTRACTS: Lazy<HashMap<(u64, H160), ContractAddress>> = Lazy::new(|| {
+	CONTRACT_ADDRESSES.iter()
+		.map(|((chain_id, contract), (address, _))| ((*chain_id, *address), contract.clone()))
+		.collect()
+});
+
 impl ContractAddress {
-	fn contract_and_address() -> HashMap<ContractAddress, H160> {
-		hashmap! {
-			ContractAddress::TUSD => H160::from_str("0000000000085d4780B73119b644AE5ecd22b376").unwrap(),
-			ContractAddress::LINK => H160::from_str("514910771af9ca656af840dff83e8264ecf986ca").unwrap(),
-			ContractAddress::BNB => H160::from_str("B8c77482e45F1F44dE1745F52C74426C631bDD52").unwrap(),
-			ContractAddress::USDC => H160::from_str("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap(),
-			ContractAddress::WBTC => H160::from_str("2260fac5e5542a773aa44fbcfedf7c193bc2c599").unwrap(),
-			ContractAddress::cDAI => H160::from_str("5d3a536E4D6DbD6114cc1Ead35777bAB948E3643").unwrap(),
-			ContractAddress::OKB => H160::from_str("75231f58b43240c9718dd58b4967c5114342a86c").unwrap(),
-			ContractAddress::CRO => H160::from_str("a0b73e1ff0b80914ab6fe0444e65848c4c34450b").unwrap(),
-			ContractAddress::WFIL => H160::from_str("6e1A19F235bE7ED8E3369eF73b196C07257494DE").unwrap(),
-			ContractAddress::BAT => H160::from_str("0d8775f648430679a709e98d2b0cb6250d2887ef").unwrap(),
-			ContractAddress::BUSD => H160::from_str("4fabb145d64652a948d72533023f6e7a623c7c53").unwrap(),
-			ContractAddress::USDT => H160::from_str("dac17f958d2ee523a2206206994597c13d831ec7").unwrap(),
-			ContractAddress::LEO => H160::from_str("2af5d2ad76741191d15dfe7bf6ac92d4bd912ca3").unwrap(),
-			ContractAddress::VEN => H160::from_str("d850942ef8811f2a866692a623011bde52a462c1").unwrap(),
-			ContractAddress::DAI => H160::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap(),
-			ContractAddress::UNI => H160::from_str("1f9840a85d5af5bf1d1762f925bdaddc4201f984").unwrap(),
+	/// Returns the known contracts, every variant but `Unidentified`.
+	pub fn known() -> Vec<ContractAddress> {
+		let mut resp: Vec<ContractAddress> = CONTRACT_ADDRESSES.keys()
+			.filter(|(chain_id, _)| *chain_id == chain::MAINNET)
+			.map(|(_, contract)| contract.clone())
+			.collect();
+		resp.sort_by_key(|it| it.symbol());
+		resp
+	}
+
+	/// Returns the contracts known to be deployed on a chain.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	///
+	pub fn known_on(chain_id: u64) -> Vec<ContractAddress> {
+		let mut resp: Vec<ContractAddress> = CONTRACT_ADDRESSES.keys()
+			.filter(|(id, _)| *id == chain_id)
+			.map(|(_, contract)| contract.clone())
+			.collect();
+		resp.sort_by_key(|it| it.symbol());
+		resp
+	}
+
+	/// Returns the chain ids the contract is known to be deployed on, in ascending order, none if
+	/// unidentified.
+	pub fn chains(&self) -> Vec<u64> {
+		let mut resp: Vec<u64> = CONTRACT_ADDRESSES.keys()
+			.filter(|(_, contract)| contract == self)
+			.map(|(chain_id, _)| *chain_id)
+			.collect();
+		resp.sort_unstable();
+		resp
+	}
+
+	/// Returns the contract with the address on a chain, `Unidentified` if unknown there.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	/// * `address` - The contract address.
+	///
+	pub fn from_address(chain_id: u64, address: H160) -> Self {
+		match ADDRESS_CONTRACTS.get(&(chain_id, address)) {
+			Some(contract) => contract.clone(),
+			None => ContractAddress::Unidentified(address),
 		}
 	}
-}
 
-impl From<H160> for ContractAddress {
-	fn from(address: H160) -> Self {
-		for (contract, address_v) in Self::contract_and_address() {
-			if address_v == address {
-				return contract;
-			}
+	/// Returns the contract address on a chain, `None` if it is not deployed there. The address of an
+	/// unidentified contract is returned as is.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	///
+	pub fn address(&self, chain_id: u64) -> Option<H160> {
+		match self {
+			ContractAddress::Unidentified(address) => Some(*address),
+			_ => CONTRACT_ADDRESSES.get(&(chain_id, self.clone())).map(|(address, _)| *address),
 		}
-		ContractAddress::Unidentified(address)
 	}
-}
 
-impl From<ContractAddress> for H160 {
-	fn from(contract_address: ContractAddress) -> Self {
-		for (contract, address) in ContractAddress::contract_and_address() {
-			if contract_address == contract {
-				return address;
-			}
+	/// Returns the token symbol, `None` if unidentified.
+	pub fn symbol(&self) -> Option<&'static str> {
+		match self {
+			ContractAddress::BAT => Some("BAT"),
+			ContractAddress::BNB => Some("BNB"),
+			ContractAddress::BUSD => Some("BUSD"),
+			ContractAddress::LINK => Some("LINK"),
+			ContractAddress::TUSD => Some("TUSD"),
+			ContractAddress::USDC => Some("USDC"),
+			ContractAddress::USDT => Some("USDT"),
+			ContractAddress::WBTC => Some("WBTC"),
+			ContractAddress::cDAI => Some("cDAI"),
+			ContractAddress::CRO => Some("CRO"),
+			ContractAddress::OKB => Some("OKB"),
+			ContractAddress::LEO => Some("LEO"),
+			ContractAddress::WFIL => Some("WFIL"),
+			ContractAddress::VEN => Some("VEN"),
+			ContractAddress::DAI => Some("DAI"),
+			ContractAddress::UNI => Some("UNI"),
+			ContractAddress::Unidentified(_) => None,
 		}
-		match contract_address {
-			ContractAddress::Unidentified(address) => address,
-			_ => panic!("Unexpected contract {:?}", contract_address),
+	}
+
+	/// Returns the token name, `None` if unidentified.
+	pub fn name(&self) -> Option<&'static str> {
+		match self {
+			ContractAddress::BAT => Some("Basic Attention Token"),
+			ContractAddress::BNB => Some("BNB"),
+			ContractAddress::BUSD => Some("Binance USD"),
+			ContractAddress::LINK => Some("ChainLink Token"),
+			ContractAddress::TUSD => Some("TrueUSD"),
+			ContractAddress::USDC => Some("USD Coin"),
+			ContractAddress::USDT => Some("Tether USD"),
+			ContractAddress::WBTC => Some("Wrapped BTC"),
+			ContractAddress::cDAI => Some("Compound Dai"),
+			ContractAddress::CRO => Some("Crypto.com Coin"),
+			ContractAddress::OKB => Some("OKB"),
+			ContractAddress::LEO => Some("Bitfinex LEO Token"),
+			ContractAddress::WFIL => Some("Wrapped Filecoin"),
+			ContractAddress::VEN => Some("VeChain Token"),
+			ContractAddress::DAI => Some("Dai Stablecoin"),
+			ContractAddress::UNI => Some("Uniswap"),
+			ContractAddress::Unidentified(_) => None,
 		}
 	}
+
+	/// Returns the number of decimals of the token amounts on a chain, `None` if unidentified or not
+	/// deployed there.
+	///
+	/// It may differ between chains, e.g. the Binance-Peg USDC and USDT on BSC have 18 decimals while
+	/// they have 6 everywhere else.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	///
+	/// ```
+	/// use erc20::{
+	///     chain,
+	///     erc20::ContractAddress,
+	/// };
+	///
+	/// assert_eq!(Some(6), ContractAddress::USDC.decimals(chain::MAINNET));
+	/// assert_eq!(Some(18), ContractAddress::DAI.decimals(chain::MAINNET));
+	/// assert_eq!(Some(8), ContractAddress::WBTC.decimals(chain::MAINNET));
+	/// assert_eq!(Some(18), ContractAddress::USDC.decimals(chain::BSC));
+	/// assert_eq!(None, ContractAddress::WBTC.decimals(chain::BSC));
+	/// ```
+	pub fn decimals(&self, chain_id: u64) -> Option<u8> {
+		CONTRACT_ADDRESSES.get(&(chain_id, self.clone())).map(|(_, decimals)| *decimals)
+	}
+}
+
+impl From<(u64, H160)> for ContractAddress {
+	fn from((chain_id, address): (u64, H160)) -> Self {
+		Self::from_address(chain_id, address)
+	}
 }
 
 impl Default for ContractAddress {
 	fn default() -> Self {
-		From::<H160>::from(Default::default())
+		Self::Unidentified(Default::default())
 	}
 }
@@ -1,21 +1,200 @@
-use crate::erc20::ContractAddress;
-use std::str::FromStr;
-use web3::types::H160;
+use crate::{
+	chain,
+	erc20::{
+		Approval,
+		ContractAddress,
+		ERC20Call,
+		ERC20Method,
+	},
+	selector::selector,
+	ERC20Error,
+};
+use std::{
+	convert::TryInto,
+	str::FromStr,
+};
+use web3::{
+	signing::keccak256,
+	types::{
+		H160,
+		H256,
+		U256,
+	},
+};
 
 #[test]
 fn creating_address() {
 	let tusd_address = H160::from_str("0000000000085d4780B73119b644AE5ecd22b376").unwrap();
 	assert_eq!("0x0000000000085d4780b73119b644ae5ecd22b376".to_string(), format!("{:?}", tusd_address));
 
-	let contract_address: ContractAddress = tusd_address.into();
+	let contract_address: ContractAddress = (chain::MAINNET, tusd_address).into();
 	assert_eq!(ContractAddress::TUSD, contract_address);
 
-	let tusd_from_contract: H160 = contract_address.into();
+	let tusd_from_contract = contract_address.address(chain::MAINNET).unwrap();
 	assert_eq!(tusd_address, tusd_from_contract);
 }
 
 #[test]
 fn usdc_address() {
-	let usdc_address: H160 = ContractAddress::USDC.into();
+	let usdc_address = ContractAddress::USDC.address(chain::MAINNET).unwrap();
 	assert_eq!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", format!("{:?}", usdc_address));
 }
+
+#[test]
+fn token_metadata() {
+	assert_eq!(Some(6), ContractAddress::USDC.decimals(chain::MAINNET));
+	assert_eq!(Some(6), ContractAddress::USDT.decimals(chain::MAINNET));
+	assert_eq!(Some(18), ContractAddress::DAI.decimals(chain::MAINNET));
+	assert_eq!(Some(8), ContractAddress::WBTC.decimals(chain::MAINNET));
+	assert_eq!(Some(8), ContractAddress::cDAI.decimals(chain::MAINNET));
+	assert_eq!(Some(6), ContractAddress::USDC.decimals(chain::POLYGON));
+	assert_eq!(Some(18), ContractAddress::USDT.decimals(chain::BSC));
+	assert_eq!(None, ContractAddress::BNB.decimals(chain::BSC));
+	assert_eq!(Some("Tether USD"), ContractAddress::USDT.name());
+	assert_eq!(Some("TrueUSD"), ContractAddress::TUSD.name());
+
+	let unknown = ContractAddress::Unidentified(H160::random());
+	assert_eq!((None, None, None), (unknown.symbol(), unknown.name(), unknown.decimals(chain::MAINNET)));
+	for contract in ContractAddress::known() {
+		assert!(contract.name().is_some());
+		for chain_id in contract.chains() {
+			assert!(contract.decimals(chain_id).is_some());
+		}
+	}
+}
+
+#[test]
+fn encode_transfer_call() {
+	let call = ERC20Call::Transfer {
+		to: H160::from_str("6748f50f686bfbca6fe8ad62b22228b87f31ff2b").unwrap(),
+		value: U256::from_dec_str("1000000000000000000000").unwrap(),
+	};
+	let serialized_str = "a9059cbb0000000000000000000000006748f50f686bfbca6fe8ad62b22228b87f31ff2b00000000000000000000000000000000000000000000003635c9adc5dea00000";
+	assert_eq!(hex::decode(serialized_str).unwrap(), call.encode().0);
+	assert_eq!(ERC20Method::Transfer, call.method());
+}
+
+#[test]
+fn calls_round_trip() {
+	let (owner, spender, value) = (H160::random(), H160::random(), U256::from(12345));
+	let calls = vec![
+		ERC20Call::Allowance { owner, spender },
+		ERC20Call::Approve { spender, value },
+		ERC20Call::BalanceOf { owner },
+		ERC20Call::Burn { value },
+		ERC20Call::BurnFrom { from: owner, value },
+		ERC20Call::DaiPermit {
+			holder: owner,
+			spender,
+			nonce: 1.into(),
+			expiry: 0.into(),
+			allowed: true,
+			v: 28,
+			r: H256::random(),
+			s: H256::random(),
+		},
+		ERC20Call::Decimals,
+		ERC20Call::DecreaseAllowance { spender, value },
+		ERC20Call::DomainSeparator,
+		ERC20Call::IncreaseAllowance { spender, value },
+		ERC20Call::Mint { to: owner, value },
+		ERC20Call::Name,
+		ERC20Call::Nonces { owner },
+		ERC20Call::Permit {
+			owner,
+			spender,
+			value,
+			deadline: U256::MAX,
+			v: 27,
+			r: H256::random(),
+			s: H256::random(),
+		},
+		ERC20Call::Symbol,
+		ERC20Call::TotalSupply,
+		ERC20Call::Transfer { to: spender, value },
+		ERC20Call::TransferFrom { from: owner, to: spender, value },
+	];
+	for call in calls {
+		let encoded = call.encode();
+		assert_eq!(call.method(), encoded.0.clone().into());
+		assert_eq!(Ok(call), ERC20Call::decode(&encoded.0));
+	}
+}
+
+#[test]
+fn decode_invalid_call() {
+	assert_eq!(Err(ERC20Error::UnexpectedType), ERC20Call::decode(&[]));
+	assert_eq!(Err(ERC20Error::UnexpectedType), ERC20Call::decode(&hex::decode("d0e30db0").unwrap()));
+
+	let truncated = hex::decode("a9059cbb0000000000000000000000006748f50f686bfbca6fe8ad62b22228b87f31ff2b").unwrap();
+	assert_eq!(Err(ERC20Error::UnexpectedEndOfData), ERC20Call::decode(&truncated));
+}
+
+#[test]
+fn method_selectors() {
+	let methods = vec![
+		ERC20Method::Allowance,
+		ERC20Method::Approve,
+		ERC20Method::BalanceOf,
+		ERC20Method::Burn,
+		ERC20Method::BurnFrom,
+		ERC20Method::DaiPermit,
+		ERC20Method::Decimals,
+		ERC20Method::DecreaseAllowance,
+		ERC20Method::DomainSeparator,
+		ERC20Method::IncreaseAllowance,
+		ERC20Method::Mint,
+		ERC20Method::Name,
+		ERC20Method::Nonces,
+		ERC20Method::Permit,
+		ERC20Method::Symbol,
+		ERC20Method::TotalSupply,
+		ERC20Method::Transfer,
+		ERC20Method::TransferFrom,
+	];
+	// The hard-coded selectors match the ones computed from the signatures.
+	for method in methods {
+		let signature = method.signature().unwrap();
+		let hard_coded: [u8; 4] = method.clone().try_into().unwrap();
+		assert_eq!(selector(signature), Ok(hard_coded), "{}", signature);
+		assert_eq!(keccak256(signature.as_bytes())[..4], hard_coded);
+		assert_eq!(method, hard_coded.to_vec().into());
+	}
+	assert_eq!(None, ERC20Method::Unidentified.signature());
+}
+
+#[test]
+fn decode_permit_approval() {
+	let (owner, spender) = (H160::random(), H160::random());
+	let call = ERC20Call::Permit { owner, spender, value: 5.into(), deadline: 9.into(), v: 27, r: H256::zero(), s: H256::zero() };
+	let permit = ERC20Call::decode(&call.encode().0).unwrap().permit_approval().unwrap();
+	assert_eq!(Approval { owner, spender, value: 5.into() }, permit.approval);
+	assert_eq!(None, permit.nonce);
+	assert_eq!(Some(9.into()), permit.deadline);
+
+	let call = ERC20Call::DaiPermit {
+		holder: owner,
+		spender,
+		nonce: 3.into(),
+		expiry: 9.into(),
+		allowed: false,
+		v: 27,
+		r: H256::zero(),
+		s: H256::zero(),
+	};
+	let permit = call.permit_approval().unwrap();
+	assert_eq!(Approval { owner, spender, value: U256::zero() }, permit.approval);
+	assert_eq!(Some(3.into()), permit.nonce);
+	assert_eq!(Some(9.into()), permit.deadline);
+
+	assert_eq!(Err(ERC20Error::NoApprovalTransaction), ERC20Call::TotalSupply.permit_approval());
+}
+
+#[test]
+fn decode_invalid_permit() {
+	let call = ERC20Call::Permit { owner: H160::zero(), spender: H160::zero(), value: 0.into(), deadline: 0.into(), v: 27, r: H256::zero(), s: H256::zero() };
+	let mut encoded = call.encode().0;
+	// `v` is the fifth word, which must fit in an `uint8`.
+	encoded[4 + 4 * 32 + 30] = 1;
+	assert_eq!(Err(ERC20Error::UnexpectedType), ERC20Call::decode(&encoded));
+}
@@ -11,6 +11,38 @@ use serde::{
 pub enum ERC20Error {
 	/// Returned when the transaction is not a Ethereum transfer neither an ERC20 transfer.
 	NoTransferTransaction,
+	/// Returned when the transaction is not an ERC20 approval.
+	NoApprovalTransaction,
+	/// The sender of the transaction is not available.
+	UnknownSender,
+	/// The gas used by the transaction is not in its receipt.
+	UnknownGasUsed,
+	/// The block base fee is above the max fee per gas of the transaction.
+	BaseFeeAboveMaxFee,
+	/// The receipt does not belong to the transaction.
+	ReceiptMismatch,
+	/// The raw transaction is not valid RLP, or it does not have the expected fields.
+	InvalidRlp,
+	/// The signature is missing or the signer cannot be recovered from it.
+	InvalidSignature,
+	/// The transaction is signed for another chain than the one it is taken from.
+	ChainMismatch,
+	/// The log has no transaction hash yet, or it was removed by a chain reorganization.
+	UnconfirmedLog,
+	/// The token list does not follow the schema.
+	InvalidTokenList,
+	/// The same token address is listed twice on a chain.
+	DuplicateToken,
+	/// The amount is not a plain decimal number.
+	InvalidAmount,
+	/// The amount has more decimals than can be kept.
+	PrecisionLoss,
+	/// The amounts have different decimals, they have to be rescaled first.
+	DecimalsMismatch,
+	/// The amount overflows an `U256`, or is negative.
+	AmountOverflow,
+	/// The amount is divided by zero.
+	DivisionByZero,
 	/// Unexpected size for the input.
 	UnexpectedSize,
 	/// The end of the input was found before expected.
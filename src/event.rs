@@ -0,0 +1,268 @@
+//! ERC20 event log decoding.
+
+use crate::{
+	erc20::Approval,
+	transfer::{
+		Transfer,
+		TransferStatus,
+	},
+	util::BytesToFixedNumber,
+	ERC20Error,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::convert::{
+	TryFrom,
+	TryInto,
+};
+use web3::types::{
+	Index,
+	Log,
+	TransactionReceipt,
+	H160,
+	H256,
+	U64,
+	U256,
+};
+
+/// Topic of `Transfer(address,address,uint256)`.
+pub const TRANSFER_EVENT_TOPIC: [u8; 32] = [
+	0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+	0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+];
+
+/// Topic of `Approval(address,address,uint256)`.
+pub const APPROVAL_EVENT_TOPIC: [u8; 32] = [
+	0x8c, 0x5b, 0xe1, 0xe5, 0xeb, 0xec, 0x7d, 0x5b, 0xd1, 0x4f, 0x71, 0x42, 0x7d, 0x1e, 0x84, 0xf3,
+	0xdd, 0x03, 0x14, 0xc0, 0xf7, 0xb2, 0x29, 0x1e, 0x5b, 0x20, 0x0a, 0xc8, 0xc7, 0xc3, 0xb9, 0x25,
+];
+
+/// ERC20 event log.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ERC20Event {
+	/// `Transfer(address indexed from, address indexed to, uint256 value)`.
+	Transfer(TransferLog),
+	/// `Approval(address indexed owner, address indexed spender, uint256 value)`.
+	Approval(ApprovalLog),
+}
+
+impl TryFrom<Log> for ERC20Event {
+	type Error = ERC20Error;
+
+	fn try_from(log: Log) -> Result<Self, Self::Error> {
+		match log.topics.first() {
+			Some(topic) if topic.0 == TRANSFER_EVENT_TOPIC => Ok(Self::Transfer(log.try_into()?)),
+			Some(topic) if topic.0 == APPROVAL_EVENT_TOPIC => Ok(Self::Approval(log.try_into()?)),
+			_ => Err(ERC20Error::UnexpectedType),
+		}
+	}
+}
+
+impl ERC20Event {
+	/// Returns the ERC20 events emitted in a transaction, the other logs are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `receipt` - The receipt of the transaction.
+	/// * `chain_id` - The chain id of the transaction, if known, set on the transfers.
+	///
+	pub fn from_receipt(receipt: &TransactionReceipt, chain_id: Option<u64>) -> Vec<Self> {
+		receipt.logs.iter()
+			.filter_map(|log| log.clone().try_into().ok())
+			.map(|event| match event {
+				Self::Transfer(transfer) => Self::Transfer(TransferLog { chain_id, ..transfer }),
+				approval => approval,
+			})
+			.collect()
+	}
+}
+
+/// Decodes the addresses and the value of the ERC20 events, which share the same layout.
+/// ERC721 events have the same topic, but index the third argument, so they are rejected, as are
+/// the logs removed by a chain reorganization or not yet mined, which have no transaction hash.
+fn decode_event(log: &Log, topic: [u8; 32]) -> Result<(H160, H160, U256), ERC20Error> {
+	if log.topics.first().map(|it| it.0) != Some(topic) {
+		return Err(ERC20Error::UnexpectedType);
+	}
+	if log.removed == Some(true) || log.transaction_hash.is_none() {
+		return Err(ERC20Error::UnconfirmedLog);
+	}
+	if log.topics.len() != 3 {
+		return Err(ERC20Error::UnexpectedSize);
+	}
+	let mut decoder: BytesToFixedNumber = log.data.clone().into();
+	let value = decoder.next_u256()?;
+	Ok((
+		H160::from_slice(&log.topics[1].0[12..]),
+		H160::from_slice(&log.topics[2].0[12..]),
+		value,
+	))
+}
+
+/// ERC20 transfer from a `Transfer` event log.
+///
+/// Unlike the transaction input, it also covers the transfers made by contracts. Logs without a
+/// transaction hash are rejected, so transfers are never taken for each other. A log does not
+/// carry the chain it was emitted on, so it is unknown unless it is set with `with_chain_id`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferLog {
+	#[serde(default)]
+	chain_id: Option<u64>,
+	log: Log,
+	from: H160,
+	to: H160,
+	value: U256,
+}
+
+impl TryFrom<Log> for TransferLog {
+	type Error = ERC20Error;
+
+	fn try_from(log: Log) -> Result<Self, Self::Error> {
+		let (from, to, value) = decode_event(&log, TRANSFER_EVENT_TOPIC)?;
+		Ok(Self {
+			chain_id: None,
+			log,
+			from,
+			to,
+			value,
+		})
+	}
+}
+
+impl TransferLog {
+	/// Returns the transfers emitted in a transaction, the other logs are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `receipt` - The receipt of the transaction.
+	/// * `chain_id` - The chain id of the transaction, if known.
+	///
+	pub fn from_receipt(receipt: &TransactionReceipt, chain_id: Option<u64>) -> Vec<Self> {
+		receipt.logs.iter()
+			.filter_map(|log| Self::try_from(log.clone()).ok())
+			.map(|transfer| Self { chain_id, ..transfer })
+			.collect()
+	}
+
+	/// Sets the chain id the log was emitted on.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	///
+	pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+		self.chain_id = Some(chain_id);
+		self
+	}
+
+	/// Returns the decoded log.
+	pub fn log(&self) -> &Log {
+		&self.log
+	}
+
+	/// Returns the index of the log in the block, if available.
+	pub fn log_index(&self) -> Option<U256> {
+		self.log.log_index
+	}
+}
+
+impl Transfer for TransferLog {
+	fn chain_id(&self) -> Option<u64> {
+		self.chain_id
+	}
+
+	fn from(&self) -> H160 {
+		self.from
+	}
+
+	fn to(&self) -> H160 {
+		self.to
+	}
+
+	fn contract(&self) -> Option<H160> {
+		Some(self.log.address)
+	}
+
+	fn value(&self) -> U256 {
+		self.value
+	}
+
+	fn tx_hash(&self) -> H256 {
+		// It is checked when the log is decoded.
+		self.log.transaction_hash.unwrap_or_default()
+	}
+
+	fn block_hash(&self) -> Option<H256> {
+		self.log.block_hash
+	}
+
+	fn block_number(&self) -> Option<U64> {
+		self.log.block_number
+	}
+
+	fn transaction_index(&self) -> Option<Index> {
+		self.log.transaction_index
+	}
+
+	fn status(&self) -> TransferStatus {
+		// Reverted transactions do not emit logs.
+		TransferStatus::Succeeded
+	}
+}
+
+/// ERC20 approval from an `Approval` event log.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalLog {
+	log: Log,
+	approval: Approval,
+}
+
+impl TryFrom<Log> for ApprovalLog {
+	type Error = ERC20Error;
+
+	fn try_from(log: Log) -> Result<Self, Self::Error> {
+		let (owner, spender, value) = decode_event(&log, APPROVAL_EVENT_TOPIC)?;
+		Ok(Self {
+			log,
+			approval: Approval {
+				owner,
+				spender,
+				value,
+			},
+		})
+	}
+}
+
+impl ApprovalLog {
+	/// Returns the approvals emitted in a transaction, the other logs are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `receipt` - The receipt of the transaction.
+	///
+	pub fn from_receipt(receipt: &TransactionReceipt) -> Vec<Self> {
+		receipt.logs.iter()
+			.filter_map(|log| log.clone().try_into().ok())
+			.collect()
+	}
+
+	/// Returns the decoded log.
+	pub fn log(&self) -> &Log {
+		&self.log
+	}
+
+	/// Returns the approval.
+	pub fn approval(&self) -> &Approval {
+		&self.approval
+	}
+
+	/// Returns the ERC20 contract address.
+	pub fn contract(&self) -> H160 {
+		self.log.address
+	}
+}
@@ -0,0 +1,172 @@
+use crate::{
+	chain,
+	erc20::Approval,
+	event::{
+		ApprovalLog,
+		ERC20Event,
+		TransferLog,
+		APPROVAL_EVENT_TOPIC,
+		TRANSFER_EVENT_TOPIC,
+	},
+	selector::event_topic,
+	transfer::Transfer,
+	ERC20Error,
+};
+use std::convert::{
+	TryFrom,
+	TryInto,
+};
+use web3::{
+	signing::keccak256,
+	types::{
+		Bytes,
+		Log,
+		TransactionReceipt,
+		H160,
+		H256,
+		U256,
+	},
+};
+
+fn address_topic(address: H160) -> H256 {
+	H256::from(address)
+}
+
+fn log(topics: Vec<H256>, data: &str) -> Log {
+	Log {
+		address: H160::from_low_u64_be(0xc0ffee),
+		topics,
+		data: Bytes(hex::decode(data).unwrap()),
+		block_hash: Some(H256::from_low_u64_be(1)),
+		block_number: Some(10.into()),
+		transaction_hash: Some(H256::from_low_u64_be(2)),
+		transaction_index: Some(3.into()),
+		log_index: Some(4.into()),
+		transaction_log_index: Some(0.into()),
+		log_type: None,
+		removed: None,
+	}
+}
+
+const VALUE_1000: &str = "00000000000000000000000000000000000000000000003635c9adc5dea00000";
+
+#[test]
+fn event_topics() {
+	assert_eq!(keccak256(b"Transfer(address,address,uint256)"), TRANSFER_EVENT_TOPIC);
+	assert_eq!(keccak256(b"Approval(address,address,uint256)"), APPROVAL_EVENT_TOPIC);
+	assert_eq!(
+		Ok(TRANSFER_EVENT_TOPIC),
+		event_topic("event Transfer(address indexed from, address indexed to, uint256 value)"),
+	);
+	assert_eq!(
+		Ok(APPROVAL_EVENT_TOPIC),
+		event_topic("event Approval(address indexed owner, address indexed spender, uint256 value)"),
+	);
+}
+
+#[test]
+fn decode_transfer_log() {
+	let (from, to) = (H160::random(), H160::random());
+	let topics = vec![H256(TRANSFER_EVENT_TOPIC), address_topic(from), address_topic(to)];
+
+	let resp: Result<TransferLog, ERC20Error> = log(topics, VALUE_1000).try_into();
+	assert!(resp.is_ok());
+
+	let resp = resp.unwrap();
+	assert_eq!(from, resp.from());
+	assert_eq!(to, resp.to());
+	assert_eq!(U256::from_dec_str("1000000000000000000000").unwrap(), resp.value());
+	assert_eq!(Some(H160::from_low_u64_be(0xc0ffee)), resp.contract());
+	assert_eq!(H256::from_low_u64_be(2), resp.tx_hash());
+	assert_eq!(Some(U256::from(4)), resp.log_index());
+	assert!((&resp as &dyn Transfer).is_erc20());
+}
+
+#[test]
+fn unconfirmed_logs() {
+	let topics = vec![H256(TRANSFER_EVENT_TOPIC), address_topic(H160::random()), address_topic(H160::random())];
+	let pending = Log { transaction_hash: None, ..log(topics.clone(), VALUE_1000) };
+	let removed = Log { removed: Some(true), ..log(topics.clone(), VALUE_1000) };
+	assert_eq!(Err(ERC20Error::UnconfirmedLog), TransferLog::try_from(pending.clone()));
+	assert_eq!(Err(ERC20Error::UnconfirmedLog), TransferLog::try_from(removed.clone()));
+
+	let receipt = TransactionReceipt {
+		logs: vec![pending, removed, log(topics, VALUE_1000)],
+		..Default::default()
+	};
+	assert_eq!(1, TransferLog::from_receipt(&receipt, None).len());
+
+	let topics = vec![H256(APPROVAL_EVENT_TOPIC), address_topic(H160::random()), address_topic(H160::random())];
+	let pending = Log { transaction_hash: None, ..log(topics.clone(), VALUE_1000) };
+	let removed = Log { removed: Some(true), ..log(topics, VALUE_1000) };
+	assert_eq!(Err(ERC20Error::UnconfirmedLog), ApprovalLog::try_from(pending));
+	assert_eq!(Err(ERC20Error::UnconfirmedLog), ApprovalLog::try_from(removed));
+}
+
+#[test]
+fn decode_approval_log() {
+	let (owner, spender) = (H160::random(), H160::random());
+	let topics = vec![H256(APPROVAL_EVENT_TOPIC), address_topic(owner), address_topic(spender)];
+
+	let resp: ApprovalLog = log(topics, VALUE_1000).try_into().unwrap();
+	assert_eq!(&Approval {
+		owner,
+		spender,
+		value: U256::from_dec_str("1000000000000000000000").unwrap(),
+	}, resp.approval());
+	assert_eq!(H160::from_low_u64_be(0xc0ffee), resp.contract());
+}
+
+#[test]
+fn reject_erc721_transfer_log() {
+	// ERC721 indexes the token id, so the log has four topics and no data.
+	let topics = vec![
+		H256(TRANSFER_EVENT_TOPIC),
+		address_topic(H160::random()),
+		address_topic(H160::random()),
+		H256::from_low_u64_be(7),
+	];
+	let resp: Result<TransferLog, ERC20Error> = log(topics, "").try_into();
+	assert_eq!(Err(ERC20Error::UnexpectedSize), resp);
+}
+
+#[test]
+fn reject_invalid_logs() {
+	let topics = vec![H256(APPROVAL_EVENT_TOPIC), address_topic(H160::random()), address_topic(H160::random())];
+	let resp: Result<TransferLog, ERC20Error> = log(topics.clone(), VALUE_1000).try_into();
+	assert_eq!(Err(ERC20Error::UnexpectedType), resp);
+
+	let resp: Result<ApprovalLog, ERC20Error> = log(topics, "").try_into();
+	assert_eq!(Err(ERC20Error::UnexpectedEndOfData), resp);
+
+	let resp: Result<ERC20Event, ERC20Error> = log(vec![], "").try_into();
+	assert_eq!(Err(ERC20Error::UnexpectedType), resp);
+}
+
+#[test]
+fn decode_receipt_logs() {
+	let (from, to) = (H160::random(), H160::random());
+	let receipt = TransactionReceipt {
+		logs: vec![
+			log(vec![H256(TRANSFER_EVENT_TOPIC), address_topic(from), address_topic(to)], VALUE_1000),
+			log(vec![H256::random()], VALUE_1000),
+			log(vec![H256(APPROVAL_EVENT_TOPIC), address_topic(from), address_topic(to)], VALUE_1000),
+		],
+		..Default::default()
+	};
+
+	let events = ERC20Event::from_receipt(&receipt, Some(chain::POLYGON));
+	assert_eq!(2, events.len());
+	assert!(matches!(&events[0], ERC20Event::Transfer(transfer) if transfer.chain_id() == Some(chain::POLYGON)));
+	assert!(matches!(events[1], ERC20Event::Approval(_)));
+
+	let transfers = TransferLog::from_receipt(&receipt, Some(chain::POLYGON));
+	assert_eq!(1, transfers.len());
+	assert_eq!(from, transfers[0].from());
+	assert_eq!(Some(chain::POLYGON), transfers[0].chain_id());
+	assert_eq!(None, TransferLog::from_receipt(&receipt, None)[0].chain_id());
+
+	let approvals = ApprovalLog::from_receipt(&receipt);
+	assert_eq!(1, approvals.len());
+	assert_eq!(to, approvals[0].approval().spender);
+}
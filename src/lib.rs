@@ -6,19 +6,89 @@
 extern crate serde;
 
 mod error;
+/// Solidity ABI types and values.
+pub mod abi;
+#[cfg(test)]
+mod abi_tests;
 /// A set of useful methods and abstractions.
 pub mod util;
 #[cfg(test)]
 mod util_tests;
 /// Ethereum transfer abstraction.
 pub mod transfer;
+#[cfg(test)]
+mod transfer_tests;
+/// Chain ids of the supported networks.
+pub mod chain;
 /// ERC20 specific information.
 pub mod erc20;
 #[cfg(test)]
 mod erc20_tests;
+/// Token amounts with decimals.
+pub mod amount;
+#[cfg(test)]
+mod amount_tests;
+/// Token registry built at runtime.
+pub mod registry;
+#[cfg(test)]
+mod registry_tests;
+/// Token lists import, following the tokenlists.org schema.
+pub mod tokenlist;
+#[cfg(test)]
+mod tokenlist_tests;
+/// Function selectors and event topics computed from signatures.
+pub mod selector;
+#[cfg(test)]
+mod selector_tests;
+/// ERC20 method return data decoding.
+pub mod returns;
+#[cfg(test)]
+mod returns_tests;
+/// EIP-712 typed structured data hashing.
+pub mod eip712;
+#[cfg(test)]
+mod eip712_tests;
+/// EIP-2612 permit signatures.
+pub mod permit;
+#[cfg(test)]
+mod permit_tests;
+/// Transaction classification.
+pub mod classifier;
+#[cfg(test)]
+mod classifier_tests;
+/// ERC20 event log decoding.
+pub mod event;
+#[cfg(test)]
+mod event_tests;
+/// EIP-2718 typed transaction envelopes.
+pub mod envelope;
+#[cfg(test)]
+mod envelope_tests;
 /// web3 transaction specific operations.
 pub mod transaction;
 #[cfg(test)]
 mod transaction_tests;
+/// Raw signed transaction decoding.
+pub mod raw;
+#[cfg(test)]
+mod raw_tests;
+/// Transaction signature operations.
+pub mod signature;
+#[cfg(test)]
+mod signature_tests;
+/// Offline ERC20 transfer transaction building.
+pub mod builder;
+#[cfg(test)]
+mod builder_tests;
+/// Transaction and receipt combined operations.
+pub mod receipt;
+#[cfg(test)]
+mod receipt_tests;
+/// Reconciliation of the transfers decoded from the transaction input with the emitted logs.
+pub mod reconciliation;
+#[cfg(test)]
+mod reconciliation_tests;
+#[cfg(test)]
+mod test_fixtures;
 
 pub use self::error::ERC20Error;
@@ -0,0 +1,271 @@
+//! EIP-2612 permit signatures.
+
+use crate::{
+	eip712::{
+		self,
+		Domain,
+	},
+	erc20::ERC20Call,
+	signature,
+	util::FixedNumberToBytes,
+	ERC20Error,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use web3::{
+	signing::{
+		keccak256,
+		recover,
+		Key,
+	},
+	types::{
+		H160,
+		H256,
+		U256,
+	},
+};
+
+/// The EIP-712 type of EIP-2612 permits.
+pub const PERMIT_TYPE: &str = "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+/// The EIP-712 type of DAI permits.
+pub const DAI_PERMIT_TYPE: &str = "Permit(address holder,address spender,uint256 nonce,uint256 expiry,bool allowed)";
+
+/// Signature of a permit, as passed to the `permit` method.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermitSignature {
+	/// The recovery id, `27` or `28`.
+	pub v: u8,
+	/// The signature `r`.
+	pub r: H256,
+	/// The signature `s`.
+	pub s: H256,
+}
+
+/// Permit message signed by the token owner to approve a spender without a transaction.
+///
+/// ```
+/// use erc20::{
+///     eip712::Domain,
+///     erc20::ERC20Call,
+///     permit::Permit,
+/// };
+/// use secp256k1::SecretKey;
+/// use web3::{
+///     signing::{
+///         Key,
+///         SecretKeyRef,
+///     },
+///     types::H160,
+/// };
+///
+/// let key = SecretKey::from_slice(&[0x46; 32]).unwrap();
+/// let owner = SecretKeyRef::new(&key).address();
+/// let domain = Domain::new("USD Coin", "2", 1, H160::from_low_u64_be(0x70c3e));
+/// let permit = Permit::EIP2612 {
+///     owner,
+///     spender: H160::from_low_u64_be(1),
+///     value: 100.into(),
+///     nonce: 0.into(),
+///     deadline: 1_700_000_000.into(),
+/// };
+/// let signature = permit.sign(&domain, SecretKeyRef::new(&key)).unwrap();
+/// assert!(permit.verify(&domain, &signature));
+///
+/// // The permit is decoded back from the `permit` invocation, given the owner nonce.
+/// let call = permit.call(&signature);
+/// assert!(matches!(call, ERC20Call::Permit { .. }));
+/// assert_eq!(Ok((permit, signature)), Permit::from_call(&call, 0.into()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Permit {
+	/// EIP-2612 permit, e.g. USDC and UNI.
+	EIP2612 {
+		/// The account holding the tokens.
+		owner: H160,
+		/// The account allowed to withdraw them.
+		spender: H160,
+		/// The amount allowed.
+		value: U256,
+		/// The owner nonce.
+		nonce: U256,
+		/// The timestamp after which the signature expires.
+		deadline: U256,
+	},
+	/// DAI permit, approving either an unlimited amount or nothing.
+	Dai {
+		/// The account holding the tokens.
+		holder: H160,
+		/// The account allowed to withdraw them.
+		spender: H160,
+		/// The holder nonce.
+		nonce: U256,
+		/// The timestamp after which the signature expires, zero if it never expires.
+		expiry: U256,
+		/// Whether the allowance is unlimited or revoked.
+		allowed: bool,
+	},
+}
+
+impl Permit {
+	/// Returns the permit and its signature from a `permit` invocation.
+	///
+	/// # Arguments
+	///
+	/// * `call` - The `permit` invocation.
+	/// * `nonce` - The owner nonce the permit was signed with, as returned by `nonces` before the
+	///   invocation. DAI permits carry their own nonce, so it is ignored for them.
+	///
+	pub fn from_call(call: &ERC20Call, nonce: U256) -> Result<(Self, PermitSignature), ERC20Error> {
+		match call {
+			ERC20Call::Permit { owner, spender, value, deadline, v, r, s } => Ok((
+				Permit::EIP2612 {
+					owner: *owner,
+					spender: *spender,
+					value: *value,
+					nonce,
+					deadline: *deadline,
+				},
+				PermitSignature { v: *v, r: *r, s: *s },
+			)),
+			ERC20Call::DaiPermit { holder, spender, nonce, expiry, allowed, v, r, s } => Ok((
+				Permit::Dai {
+					holder: *holder,
+					spender: *spender,
+					nonce: *nonce,
+					expiry: *expiry,
+					allowed: *allowed,
+				},
+				PermitSignature { v: *v, r: *r, s: *s },
+			)),
+			_ => Err(ERC20Error::NoApprovalTransaction),
+		}
+	}
+
+	/// Returns the `permit` invocation submitting the signed permit.
+	///
+	/// # Arguments
+	///
+	/// * `signature` - The owner signature.
+	///
+	pub fn call(&self, signature: &PermitSignature) -> ERC20Call {
+		let PermitSignature { v, r, s } = signature.clone();
+		match self.clone() {
+			Permit::EIP2612 { owner, spender, value, deadline, .. } => ERC20Call::Permit {
+				owner,
+				spender,
+				value,
+				deadline,
+				v,
+				r,
+				s,
+			},
+			Permit::Dai { holder, spender, nonce, expiry, allowed } => ERC20Call::DaiPermit {
+				holder,
+				spender,
+				nonce,
+				expiry,
+				allowed,
+				v,
+				r,
+				s,
+			},
+		}
+	}
+
+	/// Returns the account holding the tokens, which signs the permit.
+	pub fn owner(&self) -> H160 {
+		match self {
+			Permit::EIP2612 { owner, .. } => *owner,
+			Permit::Dai { holder, .. } => *holder,
+		}
+	}
+
+	/// Returns the EIP-712 hash of the permit, `hashStruct(permit)`.
+	pub fn struct_hash(&self) -> H256 {
+		let mut encoder: FixedNumberToBytes = Default::default();
+		match self {
+			Permit::EIP2612 { owner, spender, value, nonce, deadline } => {
+				encoder.push_vec(&keccak256(PERMIT_TYPE.as_bytes()));
+				encoder.push_h160(owner);
+				encoder.push_h160(spender);
+				encoder.push_u256(value);
+				encoder.push_u256(nonce);
+				encoder.push_u256(deadline);
+			}
+			Permit::Dai { holder, spender, nonce, expiry, allowed } => {
+				encoder.push_vec(&keccak256(DAI_PERMIT_TYPE.as_bytes()));
+				encoder.push_h160(holder);
+				encoder.push_h160(spender);
+				encoder.push_u256(nonce);
+				encoder.push_u256(expiry);
+				encoder.push_bool(*allowed);
+			}
+		}
+		H256(keccak256(&Vec::from(encoder)))
+	}
+
+	/// Returns the digest signed by the owner.
+	///
+	/// # Arguments
+	///
+	/// * `domain` - The token signing domain.
+	///
+	pub fn digest(&self, domain: &Domain) -> H256 {
+		eip712::digest(domain, &self.struct_hash())
+	}
+
+	/// Signs the permit.
+	///
+	/// # Arguments
+	///
+	/// * `domain` - The token signing domain.
+	/// * `key` - The owner private key, e.g. a `web3::signing::SecretKeyRef`.
+	///
+	pub fn sign<K: Key>(&self, domain: &Domain, key: K) -> Result<PermitSignature, ERC20Error> {
+		let signature = key.sign_message(self.digest(domain).as_bytes())
+			.map_err(|_| ERC20Error::InvalidSignature)?;
+		Ok(PermitSignature {
+			v: signature.v as u8 + 27,
+			r: signature.r,
+			s: signature.s,
+		})
+	}
+
+	/// Recovers the account that signed the permit. As `ecrecover` in the token contract, only a `v`
+	/// of `27` or `28` is accepted, and a high `s` is rejected as OpenZeppelin's `ECDSA` does.
+	///
+	/// # Arguments
+	///
+	/// * `domain` - The token signing domain.
+	/// * `signature` - The permit signature.
+	///
+	pub fn signer(&self, domain: &Domain, signature: &PermitSignature) -> Result<H160, ERC20Error> {
+		let recovery_id = match signature.v {
+			27 | 28 => signature.v as i32 - 27,
+			_ => return Err(ERC20Error::InvalidSignature),
+		};
+		if !signature::is_low_s(&U256::from_big_endian(signature.s.as_bytes())) {
+			return Err(ERC20Error::InvalidSignature);
+		}
+		let mut compact = [0u8; 64];
+		compact[..32].copy_from_slice(signature.r.as_bytes());
+		compact[32..].copy_from_slice(signature.s.as_bytes());
+		recover(self.digest(domain).as_bytes(), &compact, recovery_id).map_err(|_| ERC20Error::InvalidSignature)
+	}
+
+	/// Returns whether the permit is signed by its owner.
+	///
+	/// # Arguments
+	///
+	/// * `domain` - The token signing domain.
+	/// * `signature` - The permit signature.
+	///
+	pub fn verify(&self, domain: &Domain, signature: &PermitSignature) -> bool {
+		self.signer(domain, signature) == Ok(self.owner())
+	}
+}
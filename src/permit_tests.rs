@@ -0,0 +1,124 @@
+use crate::{
+	eip712::Domain,
+	erc20::ERC20Call,
+	permit::{
+		Permit,
+		PermitSignature,
+		DAI_PERMIT_TYPE,
+		PERMIT_TYPE,
+	},
+	ERC20Error,
+};
+use secp256k1::SecretKey;
+use std::str::FromStr;
+use web3::{
+	signing::{
+		Key,
+		SecretKeyRef,
+	},
+	types::{
+		H160,
+		H256,
+		U256,
+	},
+};
+
+fn key() -> SecretKey {
+	SecretKey::from_slice(&[0x46; 32]).unwrap()
+}
+
+fn owner() -> H160 {
+	SecretKeyRef::new(&key()).address()
+}
+
+fn dai_domain() -> Domain {
+	Domain::new("Dai Stablecoin", "1", 1, H160::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap())
+}
+
+fn usdc_domain() -> Domain {
+	Domain::new("USD Coin", "2", 1, H160::from_str("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap())
+}
+
+fn permit() -> Permit {
+	Permit::EIP2612 {
+		owner: owner(),
+		spender: H160::from_low_u64_be(1),
+		value: 1_000_000.into(),
+		nonce: 0.into(),
+		deadline: 1_700_000_000.into(),
+	}
+}
+
+fn dai_permit() -> Permit {
+	Permit::Dai {
+		holder: owner(),
+		spender: H160::from_low_u64_be(1),
+		nonce: 0.into(),
+		expiry: 0.into(),
+		allowed: true,
+	}
+}
+
+#[test]
+fn permit_type_hashes() {
+	assert_eq!(
+		"6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9",
+		hex::encode(web3::signing::keccak256(PERMIT_TYPE.as_bytes())),
+	);
+	assert_eq!(
+		"ea2aa0a1be11a07ed86d755c93467f4f82362b452371d1ba94d1715123511acb",
+		hex::encode(web3::signing::keccak256(DAI_PERMIT_TYPE.as_bytes())),
+	);
+}
+
+#[test]
+fn sign_and_verify_permit() {
+	for (permit, domain) in [(permit(), usdc_domain()), (dai_permit(), dai_domain())].iter() {
+		let signature = permit.sign(domain, SecretKeyRef::new(&key())).unwrap();
+		assert!(signature.v == 27 || signature.v == 28);
+		assert_eq!(Ok(owner()), permit.signer(domain, &signature));
+		assert!(permit.verify(domain, &signature));
+
+		// The signature is bound to the domain.
+		assert!(!permit.verify(&Domain { chain_id: Some(5.into()), ..domain.clone() }, &signature));
+
+		let decoded = ERC20Call::decode(&permit.call(&signature).encode().0).unwrap();
+		assert_eq!(Ok((permit.clone(), signature)), Permit::from_call(&decoded, 0.into()));
+	}
+}
+
+#[test]
+fn tampered_permit() {
+	let signature = permit().sign(&usdc_domain(), SecretKeyRef::new(&key())).unwrap();
+	let tampered = match permit() {
+		Permit::EIP2612 { owner, spender, nonce, deadline, .. } => Permit::EIP2612 {
+			owner,
+			spender,
+			value: 2_000_000.into(),
+			nonce,
+			deadline,
+		},
+		permit => permit,
+	};
+	assert!(!tampered.verify(&usdc_domain(), &signature));
+
+	let invalid = PermitSignature { v: 29, ..signature.clone() };
+	assert_eq!(Err(ERC20Error::InvalidSignature), permit().signer(&usdc_domain(), &invalid));
+	// The contract `ecrecover` does not take the y-parity form of `v`.
+	let parity = PermitSignature { v: signature.v - 27, ..signature.clone() };
+	assert_eq!(Err(ERC20Error::InvalidSignature), permit().signer(&usdc_domain(), &parity));
+
+	// `n - s` with the other `v` recovers the owner too, but it is not accepted.
+	let order = U256::from_str("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+	let mut high_s = [0u8; 32];
+	(order - U256::from_big_endian(signature.s.as_bytes())).to_big_endian(&mut high_s);
+	let malleable = PermitSignature { v: 55 - signature.v, r: signature.r, s: H256(high_s) };
+	assert_eq!(Err(ERC20Error::InvalidSignature), permit().signer(&usdc_domain(), &malleable));
+	let zero = PermitSignature { v: 27, r: H256::zero(), s: H256::zero() };
+	assert_eq!(Err(ERC20Error::InvalidSignature), permit().signer(&usdc_domain(), &zero));
+}
+
+#[test]
+fn permit_from_other_call() {
+	assert_eq!(Err(ERC20Error::NoApprovalTransaction), Permit::from_call(&ERC20Call::TotalSupply, 0.into()));
+}
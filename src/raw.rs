@@ -0,0 +1,223 @@
+//! Raw signed transaction decoding.
+
+use crate::{
+	classifier::TransactionClassifier,
+	envelope::TransactionType,
+	signature::recover_sender,
+	transaction::ParsedTransaction,
+	ERC20Error,
+};
+use rlp::{
+	DecoderError,
+	Rlp,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::convert::{
+	TryFrom,
+	TryInto,
+};
+use web3::{
+	signing::keccak256,
+	types::{
+		AccessList,
+		AccessListItem,
+		Bytes,
+		Transaction,
+		H160,
+		H256,
+		U256,
+		U64,
+	},
+};
+
+impl From<DecoderError> for ERC20Error {
+	fn from(_: DecoderError) -> Self {
+		ERC20Error::InvalidRlp
+	}
+}
+
+/// Signed transaction decoded from its raw bytes, as received from a wallet before broadcast.
+///
+/// The transaction has no block information, its hash is computed locally and its sender is recovered
+/// from the signature, being `None` if the signature is not valid.
+///
+/// ```
+/// use erc20::{
+///     raw::SignedTransaction,
+///     transaction::ParsedTransaction,
+/// };
+/// use std::{
+///     convert::TryFrom,
+///     str::FromStr,
+/// };
+/// use web3::types::H160;
+///
+/// // EIP-155 example transaction.
+/// let raw = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+/// let signed = SignedTransaction::try_from(raw.as_slice()).unwrap();
+/// assert_eq!(Some(1), signed.chain_id);
+/// assert_eq!(Some(H160::from_str("3535353535353535353535353535353535353535").unwrap()), signed.transaction.to);
+///
+/// let parsed: ParsedTransaction = signed.into();
+/// assert!(matches!(parsed, ParsedTransaction::EthereumTransfer(Some(1), _)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedTransaction {
+	/// The decoded transaction.
+	pub transaction: Transaction,
+	/// The chain id, `None` for legacy transactions without EIP-155 replay protection.
+	pub chain_id: Option<u64>,
+}
+
+impl TryFrom<&[u8]> for SignedTransaction {
+	type Error = ERC20Error;
+
+	fn try_from(raw: &[u8]) -> Result<Self, Self::Error> {
+		let (transaction_type, payload) = match raw.first() {
+			None => return Err(ERC20Error::InvalidRlp),
+			// An RLP list starts at 0xc0, anything below is an EIP-2718 type byte.
+			Some(first) if *first >= 0xc0 => (TransactionType::Legacy, raw),
+			// Legacy transactions have no type byte, so 0x00 is not accepted either.
+			Some(0x01) => (TransactionType::AccessList, &raw[1..]),
+			Some(0x02) => (TransactionType::DynamicFee, &raw[1..]),
+			Some(_) => return Err(ERC20Error::UnexpectedType),
+		};
+		let rlp = Rlp::new(payload);
+		if !rlp.is_list() {
+			return Err(ERC20Error::InvalidRlp);
+		}
+		// Trailing bytes would change the hash, which has to be the one of the canonical encoding.
+		let info = rlp.payload_info()?;
+		if info.header_len + info.value_len != payload.len() {
+			return Err(ERC20Error::InvalidRlp);
+		}
+		let mut resp = match transaction_type {
+			TransactionType::Legacy => decode_legacy(&rlp)?,
+			TransactionType::AccessList => decode_access_list(&rlp)?,
+			TransactionType::DynamicFee => decode_dynamic_fee(&rlp)?,
+		};
+		resp.transaction.hash = H256(keccak256(raw));
+		resp.transaction.raw = Some(Bytes(raw.to_vec()));
+		resp.transaction.from = recover_sender(&resp.transaction, resp.chain_id).ok();
+		Ok(resp)
+	}
+}
+
+impl TryFrom<Bytes> for SignedTransaction {
+	type Error = ERC20Error;
+
+	fn try_from(raw: Bytes) -> Result<Self, Self::Error> {
+		raw.0.as_slice().try_into()
+	}
+}
+
+/// Legacy transactions without a chain id are replayable on every chain, so their chain is unknown.
+impl From<SignedTransaction> for ParsedTransaction {
+	fn from(signed: SignedTransaction) -> Self {
+		let classifier = TransactionClassifier::new();
+		match signed.chain_id {
+			Some(chain_id) => classifier.with_chain_id(chain_id).classify(signed.transaction),
+			None => classifier.classify(signed.transaction),
+		}
+	}
+}
+
+fn expect_item_count(rlp: &Rlp, count: usize) -> Result<(), ERC20Error> {
+	if rlp.item_count()? != count {
+		return Err(ERC20Error::InvalidRlp);
+	}
+	Ok(())
+}
+
+fn decode_to(rlp: &Rlp, index: usize) -> Result<Option<H160>, ERC20Error> {
+	let to = rlp.at(index)?;
+	if to.is_empty() {
+		Ok(None)
+	} else {
+		Ok(Some(to.as_val()?))
+	}
+}
+
+fn decode_access_list_items(rlp: &Rlp) -> Result<AccessList, ERC20Error> {
+	let mut resp = Vec::new();
+	for item in rlp.iter() {
+		expect_item_count(&item, 2)?;
+		resp.push(AccessListItem {
+			address: item.val_at(0)?,
+			storage_keys: item.list_at(1)?,
+		});
+	}
+	Ok(resp)
+}
+
+/// `rlp([nonce, gasPrice, gasLimit, to, value, data, v, r, s])`
+fn decode_legacy(rlp: &Rlp) -> Result<SignedTransaction, ERC20Error> {
+	expect_item_count(rlp, 9)?;
+	let v: u64 = rlp.val_at(6)?;
+	Ok(SignedTransaction {
+		transaction: Transaction {
+			nonce: rlp.val_at(0)?,
+			gas_price: Some(rlp.val_at(1)?),
+			gas: rlp.val_at(2)?,
+			to: decode_to(rlp, 3)?,
+			value: rlp.val_at(4)?,
+			input: Bytes(rlp.val_at(5)?),
+			v: Some(v.into()),
+			r: Some(rlp.val_at(7)?),
+			s: Some(rlp.val_at(8)?),
+			..Default::default()
+		},
+		// EIP-155 sets `v` to `chain_id * 2 + 35` or `chain_id * 2 + 36`.
+		chain_id: if v >= 35 { Some((v - 35) / 2) } else { None },
+	})
+}
+
+/// `0x01 || rlp([chainId, nonce, gasPrice, gasLimit, to, value, data, accessList, yParity, r, s])`
+fn decode_access_list(rlp: &Rlp) -> Result<SignedTransaction, ERC20Error> {
+	expect_item_count(rlp, 11)?;
+	Ok(SignedTransaction {
+		transaction: Transaction {
+			nonce: rlp.val_at(1)?,
+			gas_price: Some(rlp.val_at(2)?),
+			gas: rlp.val_at(3)?,
+			to: decode_to(rlp, 4)?,
+			value: rlp.val_at(5)?,
+			input: Bytes(rlp.val_at(6)?),
+			access_list: Some(decode_access_list_items(&rlp.at(7)?)?),
+			v: Some(rlp.val_at::<U64>(8)?),
+			r: Some(rlp.val_at::<U256>(9)?),
+			s: Some(rlp.val_at::<U256>(10)?),
+			transaction_type: Some(1.into()),
+			..Default::default()
+		},
+		chain_id: Some(rlp.val_at(0)?),
+	})
+}
+
+/// `0x02 || rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data,
+/// accessList, yParity, r, s])`
+fn decode_dynamic_fee(rlp: &Rlp) -> Result<SignedTransaction, ERC20Error> {
+	expect_item_count(rlp, 12)?;
+	Ok(SignedTransaction {
+		transaction: Transaction {
+			nonce: rlp.val_at(1)?,
+			max_priority_fee_per_gas: Some(rlp.val_at(2)?),
+			max_fee_per_gas: Some(rlp.val_at(3)?),
+			gas: rlp.val_at(4)?,
+			to: decode_to(rlp, 5)?,
+			value: rlp.val_at(6)?,
+			input: Bytes(rlp.val_at(7)?),
+			access_list: Some(decode_access_list_items(&rlp.at(8)?)?),
+			v: Some(rlp.val_at::<U64>(9)?),
+			r: Some(rlp.val_at::<U256>(10)?),
+			s: Some(rlp.val_at::<U256>(11)?),
+			transaction_type: Some(2.into()),
+			..Default::default()
+		},
+		chain_id: Some(rlp.val_at(0)?),
+	})
+}
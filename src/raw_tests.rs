@@ -0,0 +1,167 @@
+use crate::{
+	erc20::{
+		ERC20Call,
+		ERC20Method,
+	},
+	raw::SignedTransaction,
+	test_fixtures::{
+		eip155_sender,
+		EIP155_RAW,
+	},
+	transaction::{
+		ParsedTransaction,
+		TransactionContractInvocation,
+	},
+	ERC20Error,
+};
+use rlp::RlpStream;
+use std::{
+	convert::TryFrom,
+	str::FromStr,
+};
+use web3::{
+	signing::keccak256,
+	types::{
+		AccessListItem,
+		H160,
+		H256,
+		U256,
+		U64,
+	},
+};
+
+fn token() -> H160 {
+	H160::from_low_u64_be(0x70c3e)
+}
+
+fn transfer_input() -> Vec<u8> {
+	ERC20Call::Transfer { to: H160::from_low_u64_be(1), value: 10.into() }.encode().0
+}
+
+fn append_access_list(stream: &mut RlpStream) {
+	stream.begin_list(1);
+	stream.begin_list(2);
+	stream.append(&token());
+	stream.begin_list(1);
+	stream.append(&H256::from_low_u64_be(7));
+}
+
+#[test]
+fn decode_legacy() {
+	let raw = hex::decode(EIP155_RAW).unwrap();
+	let signed = SignedTransaction::try_from(raw.as_slice()).unwrap();
+	let transaction = &signed.transaction;
+
+	assert_eq!(Some(1), signed.chain_id);
+	assert_eq!(H256(keccak256(&raw)), transaction.hash);
+	assert_eq!(U256::from(9), transaction.nonce);
+	assert_eq!(Some(U256::from(20_000_000_000u64)), transaction.gas_price);
+	assert_eq!(U256::from(21_000), transaction.gas);
+	assert_eq!(Some(H160::from_str("3535353535353535353535353535353535353535").unwrap()), transaction.to);
+	assert_eq!(U256::from_dec_str("1000000000000000000").unwrap(), transaction.value);
+	assert!(transaction.input.0.is_empty());
+	assert_eq!(Some(U64::from(37)), transaction.v);
+	assert_eq!(None, transaction.transaction_type);
+	assert_eq!(Some(eip155_sender()), transaction.from);
+	assert_eq!(Some(raw.clone().into()), transaction.raw);
+}
+
+#[test]
+fn decode_pre_eip155_legacy() {
+	let mut stream = RlpStream::new_list(9);
+	stream.append(&0u64).append(&1u64).append(&21_000u64).append(&token()).append(&0u64)
+		.append(&transfer_input()).append(&27u64).append(&U256::from(1)).append(&U256::from(2));
+	let signed = SignedTransaction::try_from(stream.out().as_ref()).unwrap();
+	assert_eq!(None, signed.chain_id);
+
+	let parsed: ParsedTransaction = signed.into();
+	assert!(matches!(
+		parsed,
+		ParsedTransaction::ContractInvocation(None, TransactionContractInvocation::ERC20(ERC20Method::Transfer, _)),
+	));
+}
+
+#[test]
+fn decode_access_list() {
+	let mut stream = RlpStream::new_list(11);
+	stream.append(&5u64).append(&3u64).append(&20u64).append(&60_000u64).append(&token()).append(&0u64)
+		.append(&transfer_input());
+	append_access_list(&mut stream);
+	stream.append(&1u64).append(&U256::from(1)).append(&U256::from(2));
+	let mut raw = vec![0x01];
+	raw.extend_from_slice(&stream.out());
+
+	let signed = SignedTransaction::try_from(raw.as_slice()).unwrap();
+	let transaction = &signed.transaction;
+	assert_eq!(Some(5), signed.chain_id);
+	assert_eq!(H256(keccak256(&raw)), transaction.hash);
+	assert_eq!(Some(U64::from(1)), transaction.transaction_type);
+	assert_eq!(Some(U256::from(20)), transaction.gas_price);
+	assert_eq!(Some(vec![AccessListItem {
+		address: token(),
+		storage_keys: vec![H256::from_low_u64_be(7)],
+	}]), transaction.access_list);
+	assert_eq!(Some(U64::from(1)), transaction.v);
+	assert_eq!(Some(5), ParsedTransaction::from(signed).chain_id());
+}
+
+#[test]
+fn decode_dynamic_fee() {
+	let mut stream = RlpStream::new_list(12);
+	stream.append(&1u64).append(&3u64).append(&2u64).append(&100u64).append(&60_000u64).append(&token())
+		.append(&0u64).append(&transfer_input());
+	append_access_list(&mut stream);
+	stream.append(&0u64).append(&U256::from(1)).append(&U256::from(2));
+	let mut raw = vec![0x02];
+	raw.extend_from_slice(&stream.out());
+
+	let signed = SignedTransaction::try_from(raw.as_slice()).unwrap();
+	let transaction = &signed.transaction;
+	assert_eq!(Some(1), signed.chain_id);
+	assert_eq!(H256(keccak256(&raw)), transaction.hash);
+	assert_eq!(Some(U64::from(2)), transaction.transaction_type);
+	assert_eq!(Some(U256::from(2)), transaction.max_priority_fee_per_gas);
+	assert_eq!(Some(U256::from(100)), transaction.max_fee_per_gas);
+	assert_eq!(U256::from(60_000), transaction.gas);
+	assert_eq!(Some(token()), transaction.to);
+	assert_eq!(transfer_input(), transaction.input.0);
+	assert_eq!(Some(U64::from(0)), transaction.v);
+
+	let parsed: ParsedTransaction = signed.into();
+	assert_eq!(Ok(ERC20Call::Transfer { to: H160::from_low_u64_be(1), value: 10.into() }), match parsed {
+		ParsedTransaction::ContractInvocation(_, invocation) => invocation.erc20_call(),
+		_ => Err(ERC20Error::UnexpectedType),
+	});
+}
+
+#[test]
+fn decode_contract_creation() {
+	let mut stream = RlpStream::new_list(9);
+	stream.append(&0u64).append(&1u64).append(&21_000u64).append_empty_data().append(&0u64)
+		.append(&hex::decode("6080604052").unwrap()).append(&27u64).append(&U256::from(1)).append(&U256::from(2));
+	let signed = SignedTransaction::try_from(stream.out().as_ref()).unwrap();
+	assert_eq!(None, signed.transaction.to);
+	assert!(matches!(ParsedTransaction::from(signed), ParsedTransaction::ContractCreation(_, _)));
+}
+
+#[test]
+fn decode_invalid_raw() {
+	assert_eq!(Err(ERC20Error::InvalidRlp), SignedTransaction::try_from(&[][..]));
+	assert_eq!(Err(ERC20Error::UnexpectedType), SignedTransaction::try_from(&[0x03, 0xc0][..]));
+	assert_eq!(Err(ERC20Error::InvalidRlp), SignedTransaction::try_from(&[0x02, 0x80][..]));
+
+	let raw = hex::decode(EIP155_RAW).unwrap();
+	assert_eq!(Err(ERC20Error::InvalidRlp), SignedTransaction::try_from(&raw[..raw.len() - 1]));
+
+	// A legacy transaction with a type byte, or with trailing bytes, is not canonical.
+	let mut typed_legacy = vec![0x00];
+	typed_legacy.extend_from_slice(&raw);
+	assert_eq!(Err(ERC20Error::UnexpectedType), SignedTransaction::try_from(typed_legacy.as_slice()));
+	let mut trailing = raw.clone();
+	trailing.push(0x00);
+	assert_eq!(Err(ERC20Error::InvalidRlp), SignedTransaction::try_from(trailing.as_slice()));
+
+	let mut stream = RlpStream::new_list(2);
+	stream.append(&1u64).append(&2u64);
+	assert_eq!(Err(ERC20Error::InvalidRlp), SignedTransaction::try_from(stream.out().as_ref()));
+}
@@ -0,0 +1,206 @@
+//! Transaction and receipt combined operations.
+
+use crate::{
+	envelope::TransactionEnvelope,
+	event::TransferLog,
+	classifier::TransactionClassifier,
+	reconciliation::{
+		self,
+		Reconciliation,
+	},
+	signature,
+	transaction::{
+		ParsedTransaction,
+		TransactionAndTransferType,
+	},
+	transfer::TransferStatus,
+	ERC20Error,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::convert::{
+	TryFrom,
+	TryInto,
+};
+use web3::types::{
+	Transaction,
+	TransactionReceipt,
+	U256,
+};
+
+/// Transaction along with its receipt, so the outcome of the execution is known.
+///
+/// Neither of them carries the chain id for typed transactions, so it should be set with
+/// `with_chain_id` to recover their sender and to identify the transferred tokens.
+///
+/// ```
+/// use erc20::{
+///     receipt::TransactionWithReceipt,
+///     transaction::TransactionAndTransferType,
+///     transfer::{
+///         Transfer,
+///         TransferStatus,
+///     },
+/// };
+/// use std::convert::TryInto;
+/// use web3::types::{
+///     H160,
+///     Transaction,
+///     TransactionReceipt,
+/// };
+///
+/// let transaction = Transaction {
+///     from: Some(H160::random()),
+///     to: Some(H160::random()),
+///     value: 10.into(),
+///     ..Default::default()
+/// };
+/// let receipt = TransactionReceipt {
+///     status: Some(0.into()),
+///     gas_used: Some(21_000.into()),
+///     effective_gas_price: Some(2.into()),
+///     ..Default::default()
+/// };
+///
+/// let with_receipt = TransactionWithReceipt::new(transaction, receipt).unwrap().with_chain_id(1);
+/// assert_eq!(Some(42_000.into()), with_receipt.fee());
+///
+/// let transfer: TransactionAndTransferType = with_receipt.try_into().unwrap();
+/// assert_eq!(TransferStatus::Reverted, transfer.status());
+/// assert_eq!(Some(1), transfer.chain_id());
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionWithReceipt {
+	#[serde(default)]
+	chain_id: Option<u64>,
+	transaction: Transaction,
+	receipt: TransactionReceipt,
+}
+
+impl TransactionWithReceipt {
+	/// Combines the transaction with its receipt, checking they match.
+	///
+	/// # Arguments
+	///
+	/// * `transaction` - The transaction.
+	/// * `receipt` - The receipt for `transaction`.
+	///
+	pub fn new(transaction: Transaction, receipt: TransactionReceipt) -> Result<Self, ERC20Error> {
+		if transaction.hash != receipt.transaction_hash {
+			return Err(ERC20Error::ReceiptMismatch);
+		}
+		Ok(Self {
+			chain_id: None,
+			transaction,
+			receipt,
+		})
+	}
+
+	/// Sets the chain id of the transaction.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	///
+	pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+		self.chain_id = Some(chain_id);
+		self
+	}
+
+	/// Returns the chain id of the transaction, if it is set or the transaction is a legacy one
+	/// signed with EIP-155 replay protection.
+	pub fn chain_id(&self) -> Option<u64> {
+		self.chain_id.or_else(|| signature::legacy_chain_id(&self.transaction))
+	}
+
+	/// Returns the transaction.
+	pub fn transaction(&self) -> &Transaction {
+		&self.transaction
+	}
+
+	/// Returns the receipt.
+	pub fn receipt(&self) -> &TransactionReceipt {
+		&self.receipt
+	}
+
+	/// Returns the execution status from the receipt.
+	pub fn status(&self) -> TransferStatus {
+		match self.receipt.status {
+			Some(status) if status.is_zero() => TransferStatus::Reverted,
+			Some(_) => TransferStatus::Succeeded,
+			None => TransferStatus::Unknown,
+		}
+	}
+
+	/// Returns the gas used by the transaction, if available.
+	pub fn gas_used(&self) -> Option<U256> {
+		self.receipt.gas_used
+	}
+
+	/// Returns the price paid per gas unit, if available.
+	/// When the receipt does not have it, the transaction gas price is used, which is only
+	/// right for legacy transactions, see `effective_fee` for the other ones.
+	pub fn effective_gas_price(&self) -> Option<U256> {
+		self.receipt.effective_gas_price.or(self.transaction.gas_price)
+	}
+
+	/// Returns the Ether fee paid for the transaction, computed from its gas pricing and the
+	/// block base fee. It fails with `UnknownGasUsed` if the receipt does not have the gas used,
+	/// or with `BaseFeeAboveMaxFee` if the base fee is above the max fee of the transaction.
+	///
+	/// # Arguments
+	///
+	/// * `base_fee` - The block base fee per gas, zero before EIP-1559.
+	///
+	pub fn effective_fee(&self, base_fee: U256) -> Result<U256, ERC20Error> {
+		let envelope = TransactionEnvelope::try_from(&self.transaction)?;
+		let gas_used = self.gas_used().ok_or(ERC20Error::UnknownGasUsed)?;
+		envelope.gas_pricing.fee(gas_used, base_fee).ok_or(ERC20Error::BaseFeeAboveMaxFee)
+	}
+
+	/// Returns the Ether fee paid for the transaction, it is paid even if it was reverted.
+	pub fn fee(&self) -> Option<U256> {
+		self.gas_used()?.checked_mul(self.effective_gas_price()?)
+	}
+
+	/// Returns the ERC20 transfers from the receipt logs, which are empty for reverted transactions.
+	pub fn log_transfers(&self) -> Vec<TransferLog> {
+		TransferLog::from_receipt(&self.receipt, self.chain_id())
+	}
+
+	/// Classifies the transaction on its chain.
+	fn parsed_transaction(&self) -> ParsedTransaction {
+		let classifier = TransactionClassifier::new();
+		match self.chain_id {
+			Some(chain_id) => classifier.with_chain_id(chain_id).classify(self.transaction.clone()),
+			None => classifier.classify(self.transaction.clone()),
+		}
+	}
+
+	/// Pairs the transfer decoded from the transaction input with the transfer logs.
+	/// A reverted transaction transfers nothing, so only its logs, if any, are reported.
+	pub fn reconcile(&self) -> Result<Vec<Reconciliation>, ERC20Error> {
+		let transfer: Option<TransactionAndTransferType> = match self.status() {
+			TransferStatus::Reverted => None,
+			_ => match self.parsed_transaction().try_into() {
+				Ok(transfer) => Some(transfer),
+				Err(ERC20Error::NoTransferTransaction) => None,
+				Err(err) => return Err(err),
+			},
+		};
+		reconciliation::reconcile(transfer.as_ref(), self.log_transfers())
+	}
+}
+
+impl TryFrom<TransactionWithReceipt> for TransactionAndTransferType {
+	type Error = ERC20Error;
+
+	fn try_from(value: TransactionWithReceipt) -> Result<Self, Self::Error> {
+		let status = value.status();
+		let transfer: TransactionAndTransferType = value.parsed_transaction().try_into()?;
+		Ok(transfer.with_status(status))
+	}
+}
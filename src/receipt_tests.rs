@@ -0,0 +1,123 @@
+use crate::{
+	chain,
+	erc20::ERC20Call,
+	event::TRANSFER_EVENT_TOPIC,
+	receipt::TransactionWithReceipt,
+	transaction::TransactionAndTransferType,
+	transfer::{
+		Transfer,
+		TransferStatus,
+	},
+	ERC20Error,
+};
+use std::convert::TryInto;
+use web3::types::{
+	Bytes,
+	Log,
+	Transaction,
+	TransactionReceipt,
+	H160,
+	H256,
+	U256,
+};
+
+fn erc20_transfer(to: H160, value: U256) -> Transaction {
+	Transaction {
+		hash: H256::from_low_u64_be(1),
+		from: Some(H160::random()),
+		to: Some(H160::random()),
+		gas_price: Some(5.into()),
+		input: ERC20Call::Transfer { to, value }.encode(),
+		..Default::default()
+	}
+}
+
+fn receipt(status: Option<u64>) -> TransactionReceipt {
+	TransactionReceipt {
+		transaction_hash: H256::from_low_u64_be(1),
+		status: status.map(Into::into),
+		gas_used: Some(50_000.into()),
+		..Default::default()
+	}
+}
+
+#[test]
+fn succeeded_transfer() {
+	let to = H160::random();
+	let with_receipt = TransactionWithReceipt::new(erc20_transfer(to, 10.into()), receipt(Some(1))).unwrap();
+	assert_eq!(TransferStatus::Succeeded, with_receipt.status());
+	assert_eq!(Some(U256::from(50_000)), with_receipt.gas_used());
+	// With no effective gas price in the receipt the transaction gas price is used.
+	assert_eq!(Some(U256::from(5)), with_receipt.effective_gas_price());
+	assert_eq!(Some(U256::from(250_000)), with_receipt.fee());
+
+	let transfer: TransactionAndTransferType = with_receipt.try_into().unwrap();
+	assert_eq!(TransferStatus::Succeeded, transfer.status());
+	assert_eq!(to, transfer.to());
+	assert!(!(&transfer as &dyn Transfer).is_reverted());
+}
+
+#[test]
+fn reverted_transfer() {
+	let mut reverted = receipt(Some(0));
+	reverted.effective_gas_price = Some(7.into());
+	let with_receipt = TransactionWithReceipt::new(erc20_transfer(H160::random(), 10.into()), reverted).unwrap();
+	assert_eq!(TransferStatus::Reverted, with_receipt.status());
+	assert_eq!(Some(U256::from(350_000)), with_receipt.fee());
+	assert!(with_receipt.log_transfers().is_empty());
+
+	let transfers: Vec<TransactionAndTransferType> = vec![
+		with_receipt.try_into().unwrap(),
+		erc20_transfer(H160::random(), 10.into()).try_into().unwrap(),
+	];
+	let not_reverted: Vec<&TransactionAndTransferType> = transfers.iter()
+		.filter(|it| !(*it as &dyn Transfer).is_reverted())
+		.collect();
+	assert_eq!(1, not_reverted.len());
+	assert_eq!(TransferStatus::Unknown, not_reverted[0].status());
+}
+
+#[test]
+fn unknown_status() {
+	let with_receipt = TransactionWithReceipt::new(erc20_transfer(H160::random(), 10.into()), receipt(None)).unwrap();
+	assert_eq!(TransferStatus::Unknown, with_receipt.status());
+}
+
+#[test]
+fn receipt_mismatch() {
+	let mut other = receipt(Some(1));
+	other.transaction_hash = H256::from_low_u64_be(2);
+	let resp = TransactionWithReceipt::new(erc20_transfer(H160::random(), 10.into()), other);
+	assert_eq!(Err(ERC20Error::ReceiptMismatch), resp);
+}
+
+#[test]
+fn receipt_log_transfers() {
+	let to = H160::random();
+	let mut succeeded = receipt(Some(1));
+	succeeded.logs.push(Log {
+		address: H160::random(),
+		topics: vec![H256(TRANSFER_EVENT_TOPIC), H256::from(H160::random()), H256::from(to)],
+		data: Bytes(H256::from_low_u64_be(10).0.to_vec()),
+		block_hash: None,
+		block_number: None,
+		transaction_hash: Some(H256::from_low_u64_be(1)),
+		transaction_index: None,
+		log_index: None,
+		transaction_log_index: None,
+		log_type: None,
+		removed: None,
+	});
+	let with_receipt = TransactionWithReceipt::new(erc20_transfer(to, 10.into()), succeeded).unwrap();
+	let transfers = with_receipt.log_transfers();
+	assert_eq!(1, transfers.len());
+	assert_eq!(to, transfers[0].to());
+	assert_eq!(TransferStatus::Succeeded, transfers[0].status());
+	assert_eq!(None, transfers[0].chain_id());
+
+	// The logs and the transfer are on the chain of the transaction.
+	let with_receipt = with_receipt.with_chain_id(chain::POLYGON);
+	assert_eq!(Some(chain::POLYGON), with_receipt.log_transfers()[0].chain_id());
+	let transfer: TransactionAndTransferType = with_receipt.try_into().unwrap();
+	assert_eq!(Some(chain::POLYGON), transfer.chain_id());
+}
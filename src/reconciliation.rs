@@ -0,0 +1,124 @@
+//! Reconciliation of the transfers decoded from the transaction input with the emitted logs.
+
+use crate::{
+	event::TransferLog,
+	transaction::TransactionAndTransferType,
+	transfer::Transfer,
+	ERC20Error,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use web3::types::{
+	H160,
+	U256,
+};
+
+/// Difference between the amount in the log and the amount in the transaction input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AmountDelta {
+	/// The log has less than the input, e.g. fee-on-transfer tokens.
+	Shortfall(U256),
+	/// The log has more than the input, e.g. rebasing tokens.
+	Excess(U256),
+}
+
+impl AmountDelta {
+	/// Computes the delta of the `logged` amount against the `expected` one.
+	///
+	/// # Arguments
+	///
+	/// * `expected` - The amount from the transaction input.
+	/// * `logged` - The amount from the log.
+	///
+	pub fn new(expected: U256, logged: U256) -> Self {
+		if logged < expected {
+			AmountDelta::Shortfall(expected - logged)
+		} else {
+			AmountDelta::Excess(logged - expected)
+		}
+	}
+}
+
+/// Outcome of pairing a transfer from the transaction input with the transfer logs.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Reconciliation {
+	/// The log has the same sender, recipient, contract, and value.
+	Match(TransferLog),
+	/// The log has the same sender, recipient, and contract, but a different value.
+	AmountMismatch {
+		/// The log found.
+		log: TransferLog,
+		/// The value from the transaction input.
+		expected: U256,
+		/// The difference of the logged value.
+		delta: AmountDelta,
+	},
+	/// No log was found for the transfer in the transaction input.
+	MissingLog {
+		/// The ERC20 contract address.
+		contract: H160,
+		/// The sender from the transaction input.
+		from: H160,
+		/// The recipient from the transaction input.
+		to: H160,
+		/// The value from the transaction input.
+		value: U256,
+	},
+	/// A log not paired with the transaction input, e.g. the fee of fee-on-transfer tokens.
+	ExtraLog(TransferLog),
+}
+
+impl Reconciliation {
+	/// Checks if the outcome is an exact match.
+	pub fn is_match(&self) -> bool {
+		matches!(self, Reconciliation::Match(_))
+	}
+}
+
+/// Pairs the ERC20 transfer decoded from the transaction input with the logs of the same transaction.
+/// Ether transfers do not emit logs, so every log is reported as extra for them, as are the logs of
+/// another transaction.
+///
+/// # Arguments
+///
+/// * `transfer` - The transfer from the transaction input, if it is a transfer.
+/// * `logs` - The transfer logs emitted by the transaction.
+///
+pub fn reconcile(
+	transfer: Option<&TransactionAndTransferType>,
+	logs: Vec<TransferLog>,
+) -> Result<Vec<Reconciliation>, ERC20Error> {
+	let mut logs = logs;
+	let mut resp = Vec::new();
+	if let Some(transfer) = transfer {
+		if let Some(contract) = transfer.contract() {
+			let (from, to, value) = transfer.get_from_to_value()?;
+			let tx_hash = transfer.tx_hash();
+			let same_parties = |log: &TransferLog| {
+				log.tx_hash() == tx_hash
+					&& log.contract() == Some(contract)
+					&& log.from() == from
+					&& log.to() == to
+			};
+			let exact = logs.iter().position(|log| same_parties(log) && log.value() == value);
+			match exact.or_else(|| logs.iter().position(same_parties)) {
+				Some(index) => {
+					let log = logs.remove(index);
+					if log.value() == value {
+						resp.push(Reconciliation::Match(log));
+					} else {
+						let delta = AmountDelta::new(value, log.value());
+						resp.push(Reconciliation::AmountMismatch { log, expected: value, delta });
+					}
+				}
+				None => resp.push(Reconciliation::MissingLog { contract, from, to, value }),
+			}
+		}
+	}
+	resp.extend(logs.into_iter().map(Reconciliation::ExtraLog));
+	Ok(resp)
+}
@@ -0,0 +1,152 @@
+use crate::{
+	erc20::ERC20Call,
+	event::TRANSFER_EVENT_TOPIC,
+	receipt::TransactionWithReceipt,
+	reconciliation::{
+		AmountDelta,
+		Reconciliation,
+	},
+	transfer::Transfer,
+};
+use web3::types::{
+	Bytes,
+	Log,
+	Transaction,
+	TransactionReceipt,
+	H160,
+	H256,
+	U256,
+};
+
+const TOKEN: u64 = 0x70c3e;
+const SENDER: u64 = 1;
+const RECIPIENT: u64 = 2;
+const FEE_COLLECTOR: u64 = 3;
+
+fn transfer_log(from: u64, to: u64, value: u64) -> Log {
+	Log {
+		address: H160::from_low_u64_be(TOKEN),
+		topics: vec![
+			H256(TRANSFER_EVENT_TOPIC),
+			H256::from_low_u64_be(from),
+			H256::from_low_u64_be(to),
+		],
+		data: Bytes(H256::from_low_u64_be(value).0.to_vec()),
+		block_hash: None,
+		block_number: None,
+		transaction_hash: Some(H256::zero()),
+		transaction_index: None,
+		log_index: None,
+		transaction_log_index: None,
+		log_type: None,
+		removed: None,
+	}
+}
+
+fn with_receipt(input: Bytes, value: u64, status: u64, logs: Vec<Log>) -> TransactionWithReceipt {
+	let transaction = Transaction {
+		from: Some(H160::from_low_u64_be(SENDER)),
+		to: Some(H160::from_low_u64_be(TOKEN)),
+		value: value.into(),
+		input,
+		..Default::default()
+	};
+	let receipt = TransactionReceipt {
+		status: Some(status.into()),
+		logs,
+		..Default::default()
+	};
+	TransactionWithReceipt::new(transaction, receipt).unwrap()
+}
+
+fn erc20_transfer(value: u64, logs: Vec<Log>) -> TransactionWithReceipt {
+	let call = ERC20Call::Transfer {
+		to: H160::from_low_u64_be(RECIPIENT),
+		value: value.into(),
+	};
+	with_receipt(call.encode(), 0, 1, logs)
+}
+
+#[test]
+fn exact_match() {
+	let resp = erc20_transfer(100, vec![transfer_log(SENDER, RECIPIENT, 100)]).reconcile().unwrap();
+	assert_eq!(1, resp.len());
+	assert!(resp[0].is_match());
+}
+
+#[test]
+fn fee_on_transfer() {
+	let logs = vec![
+		transfer_log(SENDER, FEE_COLLECTOR, 2),
+		transfer_log(SENDER, RECIPIENT, 98),
+	];
+	let resp = erc20_transfer(100, logs).reconcile().unwrap();
+	assert_eq!(2, resp.len());
+	match &resp[0] {
+		Reconciliation::AmountMismatch { log, expected, delta } => {
+			assert_eq!(U256::from(98), log.value());
+			assert_eq!(U256::from(100), *expected);
+			assert_eq!(AmountDelta::Shortfall(2.into()), *delta);
+		}
+		other => panic!("Unexpected reconciliation {:?}", other),
+	}
+	match &resp[1] {
+		Reconciliation::ExtraLog(log) => assert_eq!(H160::from_low_u64_be(FEE_COLLECTOR), log.to()),
+		other => panic!("Unexpected reconciliation {:?}", other),
+	}
+}
+
+#[test]
+fn exact_match_preferred_over_mismatch() {
+	let logs = vec![
+		transfer_log(SENDER, RECIPIENT, 1),
+		transfer_log(SENDER, RECIPIENT, 100),
+	];
+	let resp = erc20_transfer(100, logs).reconcile().unwrap();
+	assert!(resp[0].is_match());
+	assert!(matches!(resp[1], Reconciliation::ExtraLog(_)));
+}
+
+#[test]
+fn rebasing_excess() {
+	let resp = erc20_transfer(100, vec![transfer_log(SENDER, RECIPIENT, 101)]).reconcile().unwrap();
+	assert!(matches!(resp[0], Reconciliation::AmountMismatch { delta: AmountDelta::Excess(_), .. }));
+}
+
+#[test]
+fn missing_log() {
+	let resp = erc20_transfer(100, vec![]).reconcile().unwrap();
+	assert_eq!(vec![Reconciliation::MissingLog {
+		contract: H160::from_low_u64_be(TOKEN),
+		from: H160::from_low_u64_be(SENDER),
+		to: H160::from_low_u64_be(RECIPIENT),
+		value: 100.into(),
+	}], resp);
+}
+
+#[test]
+fn log_of_another_transaction() {
+	let log = Log {
+		transaction_hash: Some(H256::from_low_u64_be(1)),
+		..transfer_log(SENDER, RECIPIENT, 100)
+	};
+	let resp = erc20_transfer(100, vec![log]).reconcile().unwrap();
+	assert_eq!(2, resp.len());
+	assert!(matches!(resp[0], Reconciliation::MissingLog { .. }));
+	match &resp[1] {
+		Reconciliation::ExtraLog(log) => assert_eq!(H256::from_low_u64_be(1), log.tx_hash()),
+		other => panic!("Unexpected reconciliation {:?}", other),
+	}
+}
+
+#[test]
+fn reverted_and_ether_transfers() {
+	let call = ERC20Call::Transfer { to: H160::from_low_u64_be(RECIPIENT), value: 100.into() };
+	let reverted = with_receipt(call.encode(), 0, 0, vec![]);
+	assert_eq!(Ok(vec![]), reverted.reconcile());
+
+	let ether = with_receipt(Bytes::default(), 10, 1, vec![transfer_log(SENDER, RECIPIENT, 1)]);
+	let resp = ether.reconcile().unwrap();
+	assert_eq!(1, resp.len());
+	assert!(matches!(resp[0], Reconciliation::ExtraLog(_)));
+}
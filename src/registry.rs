@@ -0,0 +1,287 @@
+//! Token registry built at runtime.
+
+use crate::{
+	amount::TokenAmount,
+	chain,
+	erc20::ContractAddress,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::collections::HashMap;
+use web3::types::{
+	H160,
+	U256,
+};
+
+/// Token known by a registry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+	/// The chain id the token is deployed on, mainnet if not configured.
+	#[serde(default = "mainnet")]
+	pub chain_id: u64,
+	/// The token contract address.
+	pub address: H160,
+	/// The token symbol.
+	pub symbol: String,
+	/// The token name, if known.
+	#[serde(default)]
+	pub name: Option<String>,
+	/// The number of decimals of the token amounts, if known.
+	#[serde(default)]
+	pub decimals: Option<u8>,
+}
+
+impl TokenInfo {
+	/// Creates a token.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id the token is deployed on.
+	/// * `address` - The token contract address.
+	/// * `symbol` - The token symbol.
+	///
+	pub fn new(chain_id: u64, address: H160, symbol: &str) -> Self {
+		Self {
+			chain_id,
+			address,
+			symbol: symbol.to_string(),
+			name: None,
+			decimals: None,
+		}
+	}
+
+	/// Sets the token name and number of decimals.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The token name.
+	/// * `decimals` - The number of decimals of the token amounts.
+	///
+	pub fn with_metadata(mut self, name: &str, decimals: u8) -> Self {
+		self.name = Some(name.to_string());
+		self.decimals = Some(decimals);
+		self
+	}
+
+	/// Returns the raw amount scaled with the token decimals, `None` if they are unknown or more than
+	/// an amount can have.
+	///
+	/// # Arguments
+	///
+	/// * `raw` - The amount in the smallest unit of the token.
+	///
+	pub fn amount(&self, raw: U256) -> Option<TokenAmount> {
+		TokenAmount::new(raw, self.decimals?).ok()
+	}
+}
+
+fn mainnet() -> u64 {
+	chain::MAINNET
+}
+
+/// Tokens queried by chain id and address or symbol, built at runtime or loaded from configuration as
+/// a list of `TokenInfo`.
+///
+/// ```
+/// use erc20::{
+///     chain,
+///     registry::{
+///         TokenInfo,
+///         TokenRegistry,
+///     },
+/// };
+/// use std::str::FromStr;
+/// use web3::types::H160;
+///
+/// let mkr = H160::from_str("9f8f72aa9304c8b593d555f12ef6589cc3a579a2").unwrap();
+/// let registry = TokenRegistry::builtin().with_token(TokenInfo::new(chain::MAINNET, mkr, "MKR"));
+///
+/// assert_eq!(Some("MKR"), registry.by_address(chain::MAINNET, &mkr).map(|it| it.symbol.as_str()));
+/// assert_eq!(Some(mkr), registry.by_symbol(chain::MAINNET, "MKR").map(|it| it.address));
+/// assert_eq!(None, registry.by_address(chain::POLYGON, &mkr));
+///
+/// // The built-in tokens are included, on every chain they are known on.
+/// let usdc = H160::from_str("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48").unwrap();
+/// assert_eq!(Some(usdc), registry.by_symbol(chain::MAINNET, "USDC").map(|it| it.address));
+/// let polygon_usdc = H160::from_str("3c499c542cef5e3811e1192ce70d8cc03d5c3359").unwrap();
+/// assert_eq!(Some(polygon_usdc), registry.by_symbol(chain::POLYGON, "USDC").map(|it| it.address));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "Vec<TokenInfo>", into = "Vec<TokenInfo>")]
+pub struct TokenRegistry {
+	tokens: HashMap<(u64, H160), TokenInfo>,
+	symbols: HashMap<(u64, String), H160>,
+}
+
+impl From<Vec<TokenInfo>> for TokenRegistry {
+	fn from(tokens: Vec<TokenInfo>) -> Self {
+		let mut resp = Self::new();
+		resp.extend(tokens);
+		resp
+	}
+}
+
+impl Extend<TokenInfo> for TokenRegistry {
+	fn extend<T: IntoIterator<Item = TokenInfo>>(&mut self, tokens: T) {
+		for token in tokens {
+			self.insert(token);
+		}
+	}
+}
+
+impl From<TokenRegistry> for Vec<TokenInfo> {
+	fn from(registry: TokenRegistry) -> Self {
+		let mut resp: Vec<TokenInfo> = registry.tokens.into_values().collect();
+		resp.sort_by_key(|it| (it.chain_id, it.address));
+		resp
+	}
+}
+
+impl TokenRegistry {
+	/// Creates an empty registry.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Creates a registry with the tokens of `ContractAddress`, on every chain they are known on, along
+	/// with their name and decimals.
+	pub fn builtin() -> Self {
+		let tokens = ContractAddress::known().into_iter()
+			.flat_map(|contract| {
+				contract.chains().into_iter()
+					.filter_map(|chain_id| {
+						let token = TokenInfo::new(chain_id, contract.address(chain_id)?, contract.symbol()?);
+						Some(token.with_metadata(contract.name()?, contract.decimals(chain_id)?))
+					})
+					.collect::<Vec<TokenInfo>>()
+			})
+			.collect::<Vec<TokenInfo>>();
+		tokens.into()
+	}
+
+	/// Adds a token, returning the token previously registered with the same chain id and address.
+	/// The symbol then refers to the last token added with it on the chain. If the replaced token
+	/// had another symbol, that symbol falls back to a remaining token with it, the one with the
+	/// lowest address, rather than to none.
+	///
+	/// # Arguments
+	///
+	/// * `token` - The token.
+	///
+	pub fn insert(&mut self, token: TokenInfo) -> Option<TokenInfo> {
+		let previous = self.tokens.insert((token.chain_id, token.address), token.clone());
+		if let Some(previous) = &previous {
+			let key = (previous.chain_id, previous.symbol.clone());
+			if self.symbols.get(&key) == Some(&previous.address) {
+				self.symbols.remove(&key);
+				let fallback = self.tokens.values()
+					.filter(|it| it.chain_id == previous.chain_id && it.symbol == previous.symbol)
+					.map(|it| it.address)
+					.min();
+				if let Some(address) = fallback {
+					self.symbols.insert(key, address);
+				}
+			}
+		}
+		self.symbols.insert((token.chain_id, token.symbol), token.address);
+		previous
+	}
+
+	/// Adds a token, replacing any token with the same chain id and address.
+	///
+	/// # Arguments
+	///
+	/// * `token` - The token.
+	///
+	pub fn with_token(mut self, token: TokenInfo) -> Self {
+		self.insert(token);
+		self
+	}
+
+	/// Returns the token with the contract address on a chain.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	/// * `address` - The token contract address.
+	///
+	pub fn by_address(&self, chain_id: u64, address: &H160) -> Option<&TokenInfo> {
+		self.tokens.get(&(chain_id, *address))
+	}
+
+	/// Returns the token with the symbol on a chain, the symbol being case sensitive, e.g. `cDAI`.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	/// * `symbol` - The token symbol.
+	///
+	pub fn by_symbol(&self, chain_id: u64, symbol: &str) -> Option<&TokenInfo> {
+		self.symbols.get(&(chain_id, symbol.to_string()))
+			.and_then(|address| self.tokens.get(&(chain_id, *address)))
+	}
+
+	/// Returns the number of decimals of the token with the contract address on a chain, `None` if
+	/// the token or its decimals are unknown. Amounts should never be scaled with a guess.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	/// * `address` - The token contract address.
+	///
+	pub fn decimals(&self, chain_id: u64, address: &H160) -> Option<u8> {
+		self.by_address(chain_id, address)?.decimals
+	}
+
+	/// Returns the raw amount of the token with the contract address on a chain, scaled with its
+	/// decimals, `None` if they are unknown.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	/// * `address` - The token contract address.
+	/// * `raw` - The amount in the smallest unit of the token.
+	///
+	pub fn amount(&self, chain_id: u64, address: &H160, raw: U256) -> Option<TokenAmount> {
+		self.by_address(chain_id, address)?.amount(raw)
+	}
+
+	/// Checks if the contract address is a known token on a chain.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	/// * `address` - The contract address.
+	///
+	pub fn contains(&self, chain_id: u64, address: &H160) -> bool {
+		self.tokens.contains_key(&(chain_id, *address))
+	}
+
+	/// Returns the tokens of every chain, in no particular order.
+	pub fn tokens(&self) -> impl Iterator<Item = &TokenInfo> {
+		self.tokens.values()
+	}
+
+	/// Returns the tokens of a chain, in no particular order.
+	///
+	/// # Arguments
+	///
+	/// * `chain_id` - The chain id.
+	///
+	pub fn chain_tokens(&self, chain_id: u64) -> impl Iterator<Item = &TokenInfo> {
+		self.tokens.values().filter(move |it| it.chain_id == chain_id)
+	}
+
+	/// Returns the number of tokens, on every chain.
+	pub fn len(&self) -> usize {
+		self.tokens.len()
+	}
+
+	/// Checks if there are no tokens.
+	pub fn is_empty(&self) -> bool {
+		self.tokens.is_empty()
+	}
+}
@@ -0,0 +1,131 @@
+use crate::{
+	chain,
+	erc20::ContractAddress,
+	registry::{
+		TokenInfo,
+		TokenRegistry,
+	},
+};
+use std::str::FromStr;
+use web3::types::H160;
+
+fn mkr() -> TokenInfo {
+	TokenInfo::new(chain::MAINNET, H160::from_str("9f8f72aa9304c8b593d555f12ef6589cc3a579a2").unwrap(), "MKR")
+}
+
+#[test]
+fn builtin_tokens() {
+	let registry = TokenRegistry::builtin();
+	assert_eq!(ContractAddress::known().len(), registry.chain_tokens(chain::MAINNET).count());
+	for contract in ContractAddress::known() {
+		for chain_id in contract.chains() {
+			let address = contract.address(chain_id).unwrap();
+			let token = registry.by_address(chain_id, &address).unwrap();
+			assert_eq!(contract.symbol(), Some(token.symbol.as_str()));
+			assert_eq!(contract.name(), token.name.as_deref());
+			assert_eq!(contract.decimals(chain_id), token.decimals);
+			assert_eq!(Some(token), registry.by_symbol(chain_id, &token.symbol));
+		}
+	}
+	assert!(registry.by_symbol(chain::MAINNET, "cDAI").is_some());
+	assert!(registry.by_symbol(chain::MAINNET, "CDAI").is_none());
+	assert!(TokenRegistry::new().is_empty());
+
+	let usdc = registry.by_symbol(chain::BSC, "USDC").unwrap();
+	assert_eq!(Some(18), registry.decimals(chain::BSC, &usdc.address));
+	assert_eq!(None, registry.decimals(chain::MAINNET, &usdc.address));
+}
+
+#[test]
+fn chain_tokens() {
+	let registry = TokenRegistry::builtin();
+	let mainnet_usdc = registry.by_symbol(chain::MAINNET, "USDC").unwrap();
+	let arbitrum_usdc = registry.by_symbol(chain::ARBITRUM, "USDC").unwrap();
+	assert_ne!(mainnet_usdc.address, arbitrum_usdc.address);
+	assert_eq!(chain::ARBITRUM, arbitrum_usdc.chain_id);
+	assert!(!registry.contains(chain::MAINNET, &arbitrum_usdc.address));
+
+	// BNB is the native asset of BSC.
+	assert!(registry.by_symbol(chain::MAINNET, "BNB").is_some());
+	assert!(registry.by_symbol(chain::BSC, "BNB").is_none());
+
+	// DAI has the same address on Optimism and Arbitrum.
+	let dai = registry.by_symbol(chain::OPTIMISM, "DAI").unwrap();
+	assert_eq!(Some(dai.address), registry.by_symbol(chain::ARBITRUM, "DAI").map(|it| it.address));
+
+	// The same address is a different token on each chain.
+	let mut registry = TokenRegistry::new().with_token(mkr());
+	let polygon_mkr = TokenInfo::new(chain::POLYGON, mkr().address, "pMKR");
+	assert_eq!(None, registry.insert(polygon_mkr.clone()));
+	assert_eq!(2, registry.len());
+	assert_eq!(Some(&mkr()), registry.by_address(chain::MAINNET, &mkr().address));
+	assert_eq!(Some(&polygon_mkr), registry.by_address(chain::POLYGON, &mkr().address));
+	assert_eq!(vec![&polygon_mkr], registry.chain_tokens(chain::POLYGON).collect::<Vec<&TokenInfo>>());
+}
+
+#[test]
+fn add_tokens() {
+	let mut registry = TokenRegistry::new();
+	assert_eq!(None, registry.insert(mkr()));
+	assert_eq!(None, registry.decimals(chain::MAINNET, &mkr().address));
+	assert!(registry.contains(chain::MAINNET, &mkr().address));
+	assert_eq!(Some(&mkr()), registry.by_symbol(chain::MAINNET, "MKR"));
+
+	// Replacing the token updates its symbol.
+	let renamed = TokenInfo::new(chain::MAINNET, mkr().address, "MAKER").with_metadata("Maker", 18);
+	assert_eq!(Some(mkr()), registry.insert(renamed.clone()));
+	assert_eq!(None, registry.by_symbol(chain::MAINNET, "MKR"));
+	assert_eq!(Some(&renamed), registry.by_symbol(chain::MAINNET, "MAKER"));
+	assert_eq!(Some(18), registry.decimals(chain::MAINNET, &mkr().address));
+	assert_eq!(1, registry.len());
+	assert_eq!(vec![&renamed], registry.tokens().collect::<Vec<&TokenInfo>>());
+}
+
+#[test]
+fn shared_symbol() {
+	let other = TokenInfo::new(chain::MAINNET, H160::from_low_u64_be(1), "MKR");
+	let mut registry = TokenRegistry::new().with_token(mkr()).with_token(other.clone());
+	assert_eq!(Some(&other), registry.by_symbol(chain::MAINNET, "MKR"));
+
+	// Renaming the token the symbol refers to falls back to the other one still having it.
+	registry.insert(TokenInfo::new(chain::MAINNET, other.address, "OTHER"));
+	assert_eq!(Some(&mkr()), registry.by_symbol(chain::MAINNET, "MKR"));
+	assert_eq!(Some(other.address), registry.by_symbol(chain::MAINNET, "OTHER").map(|it| it.address));
+
+	// Renaming a token the symbol does not refer to keeps it.
+	let mut registry = TokenRegistry::new().with_token(mkr()).with_token(other.clone());
+	registry.insert(TokenInfo::new(chain::MAINNET, mkr().address, "MAKER"));
+	assert_eq!(Some(&other), registry.by_symbol(chain::MAINNET, "MKR"));
+}
+
+#[test]
+fn load_from_configuration() {
+	let config = r#"[
+		{ "address": "0x9f8f72aa9304c8b593d555f12ef6589cc3a579a2", "symbol": "MKR" },
+		{ "address": "0x89d24a6b4ccb1b6faa2625fe562bdd9a23260359", "symbol": "SAI" },
+		{ "chainId": 137, "address": "0x6f7c932e7684666c9fd1d44527765433e01ff61d", "symbol": "MKR" }
+	]"#;
+	let registry: TokenRegistry = serde_json::from_str(config).unwrap();
+	assert_eq!(3, registry.len());
+	assert_eq!(Some(&mkr()), registry.by_symbol(chain::MAINNET, "MKR"));
+	assert_eq!(Some(chain::POLYGON), registry.by_symbol(chain::POLYGON, "MKR").map(|it| it.chain_id));
+
+	let serialized = serde_json::to_string(&registry).unwrap();
+	assert_eq!(registry, serde_json::from_str(&serialized).unwrap());
+}
+
+#[test]
+fn contract_address_conversions() {
+	for contract in ContractAddress::known() {
+		for chain_id in contract.chains() {
+			let address = contract.address(chain_id).unwrap();
+			assert_eq!(contract, (chain_id, address).into());
+		}
+	}
+	assert_eq!(None, ContractAddress::BNB.address(chain::BSC));
+	assert_eq!(vec![chain::MAINNET], ContractAddress::TUSD.chains());
+	assert!(ContractAddress::known_on(chain::BSC).contains(&ContractAddress::BUSD));
+	let unknown = mkr().address;
+	assert_eq!(ContractAddress::Unidentified(unknown), (chain::MAINNET, unknown).into());
+	assert_eq!(None, ContractAddress::Unidentified(unknown).symbol());
+}
@@ -0,0 +1,198 @@
+//! ERC20 method return data decoding.
+
+use crate::{
+	abi::ParamType,
+	erc20::ERC20Method,
+	util::{
+		BytesToFixedNumber,
+		WORD_SIZE_256_BITS,
+	},
+	ERC20Error,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use web3::types::{
+	H256,
+	U256,
+};
+
+/// Decodes the return data of `name` or `symbol`.
+///
+/// Besides the standard `string`, it accepts the legacy `bytes32` return of tokens like MKR and SAI,
+/// padded with zeros.
+///
+/// # Arguments
+///
+/// * `data` - The return data of the call.
+///
+/// ```
+/// use erc20::returns::decode_string;
+///
+/// // `symbol()` of MKR.
+/// let data = hex::decode("4d4b520000000000000000000000000000000000000000000000000000000000").unwrap();
+/// assert_eq!(Ok("MKR".to_string()), decode_string(&data));
+///
+/// // `symbol()` of USDC.
+/// let data = hex::decode("000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000045553444300000000000000000000000000000000000000000000000000000000").unwrap();
+/// assert_eq!(Ok("USDC".to_string()), decode_string(&data));
+/// ```
+pub fn decode_string(data: &[u8]) -> Result<String, ERC20Error> {
+	if data.len() == WORD_SIZE_256_BITS {
+		let size = data.iter().rposition(|it| *it != 0).map_or(0, |it| it + 1);
+		return String::from_utf8(data[..size].to_vec()).map_err(|_| ERC20Error::UnexpectedType);
+	}
+	let mut decoder: BytesToFixedNumber = data.to_vec().into();
+	decoder.next_token(&ParamType::String)?
+		.into_string()
+		.ok_or(ERC20Error::UnexpectedType)
+}
+
+/// Decodes the return data of `decimals`, an `uint8`.
+///
+/// # Arguments
+///
+/// * `data` - The return data of the call.
+///
+pub fn decode_decimals(data: &[u8]) -> Result<u8, ERC20Error> {
+	let mut decoder: BytesToFixedNumber = data.to_vec().into();
+	decoder.next_u8()
+}
+
+/// Decoded return value of an ERC20 method call.
+///
+/// ```
+/// use erc20::{
+///     erc20::ERC20Method,
+///     returns::ERC20Return,
+/// };
+///
+/// let data = hex::decode("00000000000000000000000000000000000000000000000000000000000f4240").unwrap();
+/// assert_eq!(Ok(ERC20Return::BalanceOf(1_000_000.into())), ERC20Return::decode(&ERC20Method::BalanceOf, &data));
+///
+/// // USDT returns nothing from `transfer`, which succeeds unless it reverts.
+/// assert_eq!(Ok(ERC20Return::Transfer(true)), ERC20Return::decode(&ERC20Method::Transfer, &[]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ERC20Return {
+	/// The amount `spender` is still allowed to withdraw from `owner`.
+	Allowance(U256),
+	/// Whether the approval succeeded.
+	Approve(bool),
+	/// The balance of `owner`.
+	BalanceOf(U256),
+	/// Whether the burn succeeded.
+	Burn(bool),
+	/// Whether the burn succeeded.
+	BurnFrom(bool),
+	/// DAI's `permit` returns nothing.
+	DaiPermit,
+	/// The number of decimals.
+	Decimals(u8),
+	/// Whether the allowance decrease succeeded.
+	DecreaseAllowance(bool),
+	/// The EIP-712 domain separator.
+	DomainSeparator(H256),
+	/// Whether the allowance increase succeeded.
+	IncreaseAllowance(bool),
+	/// Whether the mint succeeded.
+	Mint(bool),
+	/// The token name.
+	Name(String),
+	/// The current permit nonce of `owner`.
+	Nonces(U256),
+	/// `permit` returns nothing.
+	Permit,
+	/// The token symbol.
+	Symbol(String),
+	/// The total token supply.
+	TotalSupply(U256),
+	/// Whether the transfer succeeded.
+	Transfer(bool),
+	/// Whether the transfer succeeded.
+	TransferFrom(bool),
+}
+
+impl ERC20Return {
+	/// Decodes the return data of a call, as returned by `eth_call`.
+	///
+	/// `transfer`, `transferFrom`, and `approve` of tokens like USDT return nothing, which is decoded
+	/// as `true` since a failure reverts. The same applies to the allowance changes, mints, and burns,
+	/// which return nothing in many tokens.
+	///
+	/// # Arguments
+	///
+	/// * `method` - The method called.
+	/// * `data` - The return data of the call.
+	///
+	pub fn decode(method: &ERC20Method, data: &[u8]) -> Result<Self, ERC20Error> {
+		let mut decoder: BytesToFixedNumber = data.to_vec().into();
+		match method {
+			ERC20Method::Allowance => Ok(ERC20Return::Allowance(decoder.next_u256()?)),
+			ERC20Method::Approve => Ok(ERC20Return::Approve(decode_success(data)?)),
+			ERC20Method::BalanceOf => Ok(ERC20Return::BalanceOf(decoder.next_u256()?)),
+			ERC20Method::Burn => Ok(ERC20Return::Burn(decode_success(data)?)),
+			ERC20Method::BurnFrom => Ok(ERC20Return::BurnFrom(decode_success(data)?)),
+			ERC20Method::DaiPermit => Ok(ERC20Return::DaiPermit),
+			ERC20Method::Decimals => Ok(ERC20Return::Decimals(decode_decimals(data)?)),
+			ERC20Method::DecreaseAllowance => Ok(ERC20Return::DecreaseAllowance(decode_success(data)?)),
+			ERC20Method::DomainSeparator => Ok(ERC20Return::DomainSeparator(decoder.next_h256()?)),
+			ERC20Method::IncreaseAllowance => Ok(ERC20Return::IncreaseAllowance(decode_success(data)?)),
+			ERC20Method::Mint => Ok(ERC20Return::Mint(decode_success(data)?)),
+			ERC20Method::Name => Ok(ERC20Return::Name(decode_string(data)?)),
+			ERC20Method::Nonces => Ok(ERC20Return::Nonces(decoder.next_u256()?)),
+			ERC20Method::Permit => Ok(ERC20Return::Permit),
+			ERC20Method::Symbol => Ok(ERC20Return::Symbol(decode_string(data)?)),
+			ERC20Method::TotalSupply => Ok(ERC20Return::TotalSupply(decoder.next_u256()?)),
+			ERC20Method::Transfer => Ok(ERC20Return::Transfer(decode_success(data)?)),
+			ERC20Method::TransferFrom => Ok(ERC20Return::TransferFrom(decode_success(data)?)),
+			ERC20Method::Unidentified => Err(ERC20Error::UnexpectedType),
+		}
+	}
+
+	/// Returns the method called.
+	pub fn method(&self) -> ERC20Method {
+		match self {
+			ERC20Return::Allowance(_) => ERC20Method::Allowance,
+			ERC20Return::Approve(_) => ERC20Method::Approve,
+			ERC20Return::BalanceOf(_) => ERC20Method::BalanceOf,
+			ERC20Return::Burn(_) => ERC20Method::Burn,
+			ERC20Return::BurnFrom(_) => ERC20Method::BurnFrom,
+			ERC20Return::DaiPermit => ERC20Method::DaiPermit,
+			ERC20Return::Decimals(_) => ERC20Method::Decimals,
+			ERC20Return::DecreaseAllowance(_) => ERC20Method::DecreaseAllowance,
+			ERC20Return::DomainSeparator(_) => ERC20Method::DomainSeparator,
+			ERC20Return::IncreaseAllowance(_) => ERC20Method::IncreaseAllowance,
+			ERC20Return::Mint(_) => ERC20Method::Mint,
+			ERC20Return::Name(_) => ERC20Method::Name,
+			ERC20Return::Nonces(_) => ERC20Method::Nonces,
+			ERC20Return::Permit => ERC20Method::Permit,
+			ERC20Return::Symbol(_) => ERC20Method::Symbol,
+			ERC20Return::TotalSupply(_) => ERC20Method::TotalSupply,
+			ERC20Return::Transfer(_) => ERC20Method::Transfer,
+			ERC20Return::TransferFrom(_) => ERC20Method::TransferFrom,
+		}
+	}
+
+	/// Returns the amount of `allowance`, `balanceOf`, `nonces`, and `totalSupply`.
+	pub fn amount(&self) -> Option<U256> {
+		match self {
+			ERC20Return::Allowance(value)
+			| ERC20Return::BalanceOf(value)
+			| ERC20Return::Nonces(value)
+			| ERC20Return::TotalSupply(value) => Some(*value),
+			_ => None,
+		}
+	}
+}
+
+/// Decodes the `bool` returned by state changing methods, being `true` if nothing is returned.
+fn decode_success(data: &[u8]) -> Result<bool, ERC20Error> {
+	if data.is_empty() {
+		return Ok(true);
+	}
+	let mut decoder: BytesToFixedNumber = data.to_vec().into();
+	decoder.next_bool()
+}
@@ -0,0 +1,120 @@
+use crate::{
+	abi::Token,
+	erc20::ERC20Method,
+	returns::{
+		decode_decimals,
+		decode_string,
+		ERC20Return,
+	},
+	util::FixedNumberToBytes,
+	ERC20Error,
+};
+use web3::types::H256;
+
+fn encoded_string(value: &str) -> Vec<u8> {
+	let mut encoder: FixedNumberToBytes = Default::default();
+	encoder.push_tokens(&[Token::String(value.to_string())]).unwrap();
+	encoder.into()
+}
+
+#[test]
+fn decode_string_returns() {
+	assert_eq!(Ok("USD Coin".to_string()), decode_string(&encoded_string("USD Coin")));
+	assert_eq!(Ok("".to_string()), decode_string(&encoded_string("")));
+
+	// A name longer than a word.
+	let name = "Wrapped liquid staked Ether 2.0 on a long name";
+	assert_eq!(Ok(name.to_string()), decode_string(&encoded_string(name)));
+}
+
+#[test]
+fn decode_bytes32_returns() {
+	// `name()` and `symbol()` of MKR.
+	let name = hex::decode("4d616b6572000000000000000000000000000000000000000000000000000000").unwrap();
+	assert_eq!(Ok("Maker".to_string()), decode_string(&name));
+	let symbol = hex::decode("4d4b520000000000000000000000000000000000000000000000000000000000").unwrap();
+	assert_eq!(Ok("MKR".to_string()), decode_string(&symbol));
+
+	// `symbol()` of SAI, which returns `DAI`.
+	let symbol = hex::decode("4441490000000000000000000000000000000000000000000000000000000000").unwrap();
+	assert_eq!(Ok("DAI".to_string()), decode_string(&symbol));
+
+	assert_eq!(Ok("".to_string()), decode_string(&[0; 32]));
+}
+
+#[test]
+fn decode_invalid_string_returns() {
+	assert_eq!(Err(ERC20Error::UnexpectedEndOfData), decode_string(&[]));
+	let mut invalid = [0u8; 32];
+	invalid[0] = 0xff;
+	assert_eq!(Err(ERC20Error::UnexpectedType), decode_string(&invalid));
+}
+
+#[test]
+fn decode_decimals_returns() {
+	let mut encoder: FixedNumberToBytes = Default::default();
+	encoder.push_usize(6);
+	assert_eq!(Ok(6), decode_decimals(&Vec::from(encoder)));
+
+	let mut encoder: FixedNumberToBytes = Default::default();
+	encoder.push_usize(256);
+	assert_eq!(Err(ERC20Error::UnexpectedType), decode_decimals(&Vec::from(encoder)));
+	assert_eq!(Err(ERC20Error::UnexpectedEndOfData), decode_decimals(&[]));
+}
+
+fn word(value: u64) -> Vec<u8> {
+	let mut encoder: FixedNumberToBytes = Default::default();
+	encoder.push_u256(&value.into());
+	encoder.into()
+}
+
+#[test]
+fn decode_read_calls() {
+	assert_eq!(Ok(ERC20Return::Allowance(5.into())), ERC20Return::decode(&ERC20Method::Allowance, &word(5)));
+	assert_eq!(Ok(ERC20Return::BalanceOf(6.into())), ERC20Return::decode(&ERC20Method::BalanceOf, &word(6)));
+	assert_eq!(Ok(ERC20Return::Nonces(7.into())), ERC20Return::decode(&ERC20Method::Nonces, &word(7)));
+	assert_eq!(Ok(ERC20Return::TotalSupply(8.into())), ERC20Return::decode(&ERC20Method::TotalSupply, &word(8)));
+	assert_eq!(Ok(ERC20Return::Decimals(18)), ERC20Return::decode(&ERC20Method::Decimals, &word(18)));
+	assert_eq!(
+		Ok(ERC20Return::DomainSeparator(H256::from_low_u64_be(9))),
+		ERC20Return::decode(&ERC20Method::DomainSeparator, &word(9)),
+	);
+	assert_eq!(
+		Ok(ERC20Return::Symbol("USDC".to_string())),
+		ERC20Return::decode(&ERC20Method::Symbol, &encoded_string("USDC")),
+	);
+	assert_eq!(
+		Ok(ERC20Return::Name("Maker".to_string())),
+		ERC20Return::decode(&ERC20Method::Name, &hex::decode("4d616b6572000000000000000000000000000000000000000000000000000000").unwrap()),
+	);
+
+	let resp = ERC20Return::decode(&ERC20Method::BalanceOf, &word(6)).unwrap();
+	assert_eq!(ERC20Method::BalanceOf, resp.method());
+	assert_eq!(Some(6.into()), resp.amount());
+	assert_eq!(None, ERC20Return::Transfer(true).amount());
+}
+
+#[test]
+fn decode_write_calls() {
+	assert_eq!(Ok(ERC20Return::Transfer(true)), ERC20Return::decode(&ERC20Method::Transfer, &word(1)));
+	assert_eq!(Ok(ERC20Return::Transfer(false)), ERC20Return::decode(&ERC20Method::Transfer, &word(0)));
+	assert_eq!(Ok(ERC20Return::TransferFrom(true)), ERC20Return::decode(&ERC20Method::TransferFrom, &word(1)));
+	assert_eq!(Ok(ERC20Return::Approve(false)), ERC20Return::decode(&ERC20Method::Approve, &word(0)));
+	assert_eq!(Ok(ERC20Return::Permit), ERC20Return::decode(&ERC20Method::Permit, &[]));
+
+	// Tokens like USDT return nothing.
+	assert_eq!(Ok(ERC20Return::Transfer(true)), ERC20Return::decode(&ERC20Method::Transfer, &[]));
+	assert_eq!(Ok(ERC20Return::TransferFrom(true)), ERC20Return::decode(&ERC20Method::TransferFrom, &[]));
+	assert_eq!(Ok(ERC20Return::Approve(true)), ERC20Return::decode(&ERC20Method::Approve, &[]));
+	assert_eq!(Ok(ERC20Return::Mint(true)), ERC20Return::decode(&ERC20Method::Mint, &[]));
+	assert_eq!(Ok(ERC20Return::Burn(false)), ERC20Return::decode(&ERC20Method::Burn, &word(0)));
+	assert_eq!(Ok(ERC20Return::IncreaseAllowance(true)), ERC20Return::decode(&ERC20Method::IncreaseAllowance, &word(1)));
+}
+
+#[test]
+fn decode_invalid_returns() {
+	assert_eq!(Err(ERC20Error::UnexpectedEndOfData), ERC20Return::decode(&ERC20Method::BalanceOf, &[]));
+	assert_eq!(Err(ERC20Error::UnexpectedEndOfData), ERC20Return::decode(&ERC20Method::TotalSupply, &[0; 31]));
+	assert_eq!(Err(ERC20Error::UnexpectedType), ERC20Return::decode(&ERC20Method::Transfer, &word(2)));
+	assert_eq!(Err(ERC20Error::UnexpectedType), ERC20Return::decode(&ERC20Method::Unidentified, &word(1)));
+}
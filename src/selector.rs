@@ -0,0 +1,243 @@
+//! Function selectors and event topics computed from signatures.
+
+use crate::ERC20Error;
+use web3::signing::keccak256;
+
+/// Modifiers that may follow the parameters of a function or event signature.
+const MODIFIERS: [&str; 11] = [
+	"external", "public", "internal", "private", "view", "pure", "payable", "nonpayable", "virtual",
+	"override", "anonymous",
+];
+
+/// Data locations that may follow the type of a parameter.
+const DATA_LOCATIONS: [&str; 3] = ["memory", "calldata", "storage"];
+
+/// Returns the canonical form of a function or event signature, as hashed for selectors and topics.
+///
+/// It accepts the human-readable forms found in Solidity sources and ABIs: the `function` and
+/// `event` keywords, parameter names, `indexed`, data locations, `payable`, whitespace, trailing
+/// modifiers, `returns (...)`, and `;`, `tuple(...)` components, and the `uint`, `int`, and `byte`
+/// aliases. Only elementary types are accepted, so structs have to be spelled as tuples, contracts
+/// as `address`, and enums as `uint8`.
+///
+/// # Arguments
+///
+/// * `signature` - The function or event signature.
+///
+/// ```
+/// use erc20::selector::normalize_signature;
+///
+/// assert_eq!(
+///     Ok("transfer(address,uint256)".to_string()),
+///     normalize_signature("function transfer(address to, uint amount) external returns (bool)"),
+/// );
+/// assert_eq!(
+///     Ok("Transfer(address,address,uint256)".to_string()),
+///     normalize_signature("event Transfer(address indexed from, address indexed to, uint256 value)"),
+/// );
+/// ```
+pub fn normalize_signature(signature: &str) -> Result<String, ERC20Error> {
+	let signature = signature.trim();
+	let signature = signature.strip_prefix("function ")
+		.or_else(|| signature.strip_prefix("event "))
+		.unwrap_or(signature);
+	let open = signature.find('(').ok_or(ERC20Error::UnexpectedType)?;
+	let name = signature[..open].trim();
+	if !is_identifier(name) {
+		return Err(ERC20Error::UnexpectedType);
+	}
+	let close = matching_paren(signature, open)?;
+	let params = normalize_params(&signature[open + 1..close])?;
+	check_modifiers(&signature[close + 1..])?;
+	Ok(format!("{}({})", name, params))
+}
+
+/// Returns the 4 bytes selector of a function, the start of its signature hash.
+///
+/// # Arguments
+///
+/// * `signature` - The function signature, normalized before hashing.
+///
+/// ```
+/// use erc20::selector::selector;
+///
+/// assert_eq!(Ok([0xa9, 0x05, 0x9c, 0xbb]), selector("transfer(address to, uint256 amount)"));
+/// ```
+pub fn selector(signature: &str) -> Result<[u8; 4], ERC20Error> {
+	let hash = keccak256(normalize_signature(signature)?.as_bytes());
+	let mut resp = [0u8; 4];
+	resp.copy_from_slice(&hash[..4]);
+	Ok(resp)
+}
+
+/// Returns the topic of an event, its signature hash, as the first topic of its logs.
+///
+/// # Arguments
+///
+/// * `signature` - The event signature, normalized before hashing.
+///
+pub fn event_topic(signature: &str) -> Result<[u8; 32], ERC20Error> {
+	Ok(keccak256(normalize_signature(signature)?.as_bytes()))
+}
+
+fn is_identifier(value: &str) -> bool {
+	!value.is_empty() && value.chars().all(|it| it.is_ascii_alphanumeric() || it == '_' || it == '$')
+}
+
+/// Checks if `value` is a number in `min..=max` multiple of `step`, written without leading zeros.
+pub(crate) fn is_size(value: &str, min: usize, max: usize, step: usize) -> bool {
+	match value.parse::<usize>() {
+		Ok(size) => !value.starts_with('0') && (min..=max).contains(&size) && size % step == 0,
+		Err(_) => false,
+	}
+}
+
+/// Checks if the type is an elementary ABI type, after the aliases are replaced.
+fn is_elementary(base: &str) -> bool {
+	match base {
+		"address" | "bool" | "string" | "bytes" | "function" => true,
+		_ => if let Some(size) = base.strip_prefix("bytes") {
+			is_size(size, 1, 32, 1)
+		} else if let Some(bits) = base.strip_prefix("uint").or_else(|| base.strip_prefix("int")) {
+			is_size(bits, 8, 256, 8)
+		} else {
+			false
+		},
+	}
+}
+
+/// Checks what follows the parameters: modifiers, then an optional `returns (...)`, and an optional
+/// `;`.
+fn check_modifiers(value: &str) -> Result<(), ERC20Error> {
+	let value = value.trim();
+	let mut rest = value.strip_suffix(';').unwrap_or(value).trim_start();
+	while !rest.is_empty() {
+		if let Some(returns) = rest.strip_prefix("returns") {
+			let returns = returns.trim_start();
+			if !returns.starts_with('(') {
+				return Err(ERC20Error::UnexpectedType);
+			}
+			let close = matching_paren(returns, 0)?;
+			normalize_params(&returns[1..close])?;
+			rest = returns[close + 1..].trim_start();
+			return if rest.is_empty() { Ok(()) } else { Err(ERC20Error::UnexpectedType) };
+		}
+		let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+		if !MODIFIERS.contains(&&rest[..end]) {
+			return Err(ERC20Error::UnexpectedType);
+		}
+		rest = rest[end..].trim_start();
+	}
+	Ok(())
+}
+
+/// Returns the index of the parenthesis closing the one at `open`.
+fn matching_paren(value: &str, open: usize) -> Result<usize, ERC20Error> {
+	let mut depth = 0usize;
+	for (index, it) in value.char_indices().skip_while(|(index, _)| *index < open) {
+		match it {
+			'(' => depth += 1,
+			')' => {
+				depth -= 1;
+				if depth == 0 {
+					return Ok(index);
+				}
+			}
+			_ => {}
+		}
+	}
+	Err(ERC20Error::UnexpectedType)
+}
+
+/// Splits the parameters on the commas outside of tuples.
+fn split_params(params: &str) -> Vec<&str> {
+	let mut resp = Vec::new();
+	let (mut depth, mut start) = (0usize, 0);
+	for (index, it) in params.char_indices() {
+		match it {
+			'(' => depth += 1,
+			')' => depth = depth.saturating_sub(1),
+			',' if depth == 0 => {
+				resp.push(&params[start..index]);
+				start = index + 1;
+			}
+			_ => {}
+		}
+	}
+	resp.push(&params[start..]);
+	resp
+}
+
+fn normalize_params(params: &str) -> Result<String, ERC20Error> {
+	if params.trim().is_empty() {
+		return Ok(String::new());
+	}
+	let params = split_params(params).into_iter()
+		.map(normalize_param)
+		.collect::<Result<Vec<String>, ERC20Error>>()?;
+	Ok(params.join(","))
+}
+
+/// Normalizes a parameter type, dropping its name and modifiers.
+fn normalize_param(param: &str) -> Result<String, ERC20Error> {
+	let param = param.trim();
+	let tuple = param.strip_prefix("tuple").filter(|it| it.trim_start().starts_with('(')).unwrap_or(param).trim_start();
+	if tuple.starts_with('(') {
+		let close = matching_paren(tuple, 0)?;
+		let components = normalize_params(&tuple[1..close])?;
+		let (suffix, rest) = array_suffix(&tuple[close + 1..])?;
+		check_param_words(rest, false)?;
+		return Ok(format!("({}){}", components, suffix));
+	}
+	let param_type = param.split_whitespace().next().ok_or(ERC20Error::UnexpectedType)?;
+	let (base, (suffix, rest)) = match param_type.find('[') {
+		Some(index) => (&param_type[..index], array_suffix(&param[index..])?),
+		None => (param_type, array_suffix(&param[param_type.len()..])?),
+	};
+	let base = match base {
+		"uint" => "uint256",
+		"int" => "int256",
+		"byte" => "bytes1",
+		_ => base,
+	};
+	if !is_elementary(base) {
+		return Err(ERC20Error::UnexpectedType);
+	}
+	check_param_words(rest, base == "address")?;
+	Ok(format!("{}{}", base, suffix))
+}
+
+/// Checks the words after a parameter type: `payable` for addresses, a data location, `indexed`,
+/// and the parameter name, each optional but in this order.
+fn check_param_words(words: &str, address: bool) -> Result<(), ERC20Error> {
+	let mut words = words.split_whitespace().peekable();
+	if address {
+		words.next_if_eq(&"payable");
+	}
+	words.next_if(|it| DATA_LOCATIONS.contains(it));
+	words.next_if_eq(&"indexed");
+	words.next_if(|it| is_identifier(it));
+	match words.next() {
+		Some(_) => Err(ERC20Error::UnexpectedType),
+		None => Ok(()),
+	}
+}
+
+/// Returns the array dimensions at the start of `value`, e.g. `[][2]`, without whitespace, and the
+/// rest of `value`.
+fn array_suffix(value: &str) -> Result<(String, &str), ERC20Error> {
+	let mut resp = String::new();
+	let mut rest = value.trim_start();
+	while let Some(dimension) = rest.strip_prefix('[') {
+		let close = dimension.find(']').ok_or(ERC20Error::UnexpectedType)?;
+		let size = dimension[..close].trim();
+		if !size.chars().all(|it| it.is_ascii_digit()) {
+			return Err(ERC20Error::UnexpectedType);
+		}
+		resp.push('[');
+		resp.push_str(size);
+		resp.push(']');
+		rest = dimension[close + 1..].trim_start();
+	}
+	Ok((resp, rest))
+}
@@ -0,0 +1,108 @@
+use crate::{
+	selector::{
+		event_topic,
+		normalize_signature,
+		selector,
+	},
+	ERC20Error,
+};
+use web3::signing::keccak256;
+
+#[test]
+fn normalize_human_readable_signatures() {
+	let cases = vec![
+		("transfer(address,uint256)", "transfer(address,uint256)"),
+		("transfer(address to, uint256 amount)", "transfer(address,uint256)"),
+		("  function transfer( address  to , uint amount ) external returns (bool) ", "transfer(address,uint256)"),
+		("function withdraw(address payable to, int value)", "withdraw(address,int256)"),
+		("setData(bytes calldata data, string memory name, byte flag)", "setData(bytes,string,bytes1)"),
+		("event Transfer(address indexed from, address indexed to, uint256 value)", "Transfer(address,address,uint256)"),
+		("totalSupply()", "totalSupply()"),
+		("totalSupply( )", "totalSupply()"),
+		("batch(address[] to, uint256 [2] values, uint[][3] matrix)", "batch(address[],uint256[2],uint256[][3])"),
+		("function balanceOf(address) public view virtual override returns (uint256);", "balanceOf(address)"),
+		("function name() external view returns(string memory)", "name()"),
+		("event Log(bytes32 indexed id, int8 delta) anonymous", "Log(bytes32,int8)"),
+		("permit(address,address,uint256,uint256,uint8,bytes32,bytes32)", "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)"),
+	];
+	for (signature, expected) in cases {
+		assert_eq!(Ok(expected.to_string()), normalize_signature(signature), "{}", signature);
+	}
+}
+
+#[test]
+fn normalize_tuples() {
+	assert_eq!(
+		Ok("fill((address,uint256,(bytes32,bool)[]),bytes)".to_string()),
+		normalize_signature("fill((address maker, uint amount, (bytes32 id, bool ok)[] legs) order, bytes signature)"),
+	);
+	assert_eq!(
+		Ok("fill((address,uint256)[2])".to_string()),
+		normalize_signature("fill(tuple(address maker, uint256 amount)[2] orders)"),
+	);
+}
+
+#[test]
+fn invalid_signatures() {
+	let signatures = [
+		"",
+		"transfer",
+		"transfer(address",
+		"(address)",
+		"trans fer(address)",
+		"transfer(address[x])",
+		"transfer(,)",
+		"transfer(address to extra junk, uint256 amount)",
+		"transfer(address to, uint256 indexed memory amount)",
+		"transfer(uint256 payable amount)",
+		"fill((address maker) order extra)",
+	];
+	for signature in signatures.iter() {
+		assert_eq!(Err(ERC20Error::UnexpectedType), normalize_signature(signature), "{}", signature);
+	}
+}
+
+#[test]
+fn invalid_suffixes() {
+	let signatures = [
+		"transfer(address,uint256) garbage",
+		"transfer(address,uint256))",
+		"transfer(address,uint256) returns bool",
+		"transfer(address,uint256) returns (bool) external",
+		"transfer(address,uint256) returns (bool) extra)",
+		"transfer(address,uint256) returns (Foo)",
+		"transfer(address,uint256);;",
+	];
+	for signature in signatures.iter() {
+		assert_eq!(Err(ERC20Error::UnexpectedType), normalize_signature(signature), "{}", signature);
+	}
+}
+
+#[test]
+fn non_elementary_types() {
+	let signatures = [
+		"fill(Order order)",
+		"transfer(IERC20 token, uint256 amount)",
+		"f(uint7)",
+		"f(uint264)",
+		"f(uint08)",
+		"f(bytes0)",
+		"f(bytes33)",
+		"f((address,Leg[]))",
+	];
+	for signature in signatures.iter() {
+		assert_eq!(Err(ERC20Error::UnexpectedType), normalize_signature(signature), "{}", signature);
+	}
+	assert_eq!(Ok("f(uint8,int256,bytes32,function)".to_string()), normalize_signature("f(uint8,int256,bytes32,function)"));
+}
+
+#[test]
+fn compute_selectors_and_topics() {
+	assert_eq!(Ok([0xa9, 0x05, 0x9c, 0xbb]), selector("transfer(address to, uint256 amount)"));
+	assert_eq!(Ok([0x09, 0x5e, 0xa7, 0xb3]), selector("function approve(address spender, uint256 amount) returns (bool)"));
+	assert_eq!(
+		Ok(keccak256(b"Transfer(address,address,uint256)")),
+		event_topic("Transfer(address indexed, address indexed, uint256)"),
+	);
+	assert_eq!(Err(ERC20Error::UnexpectedType), selector("transfer("));
+}
@@ -0,0 +1,261 @@
+//! Transaction signature operations.
+
+use crate::{
+	envelope::{
+		GasPricing,
+		TransactionEnvelope,
+		TransactionType,
+	},
+	raw::SignedTransaction,
+	ERC20Error,
+};
+use rlp::RlpStream;
+use std::convert::TryFrom;
+use web3::{
+	signing::{
+		keccak256,
+		recover,
+		Key,
+	},
+	types::{
+		AccessList,
+		Bytes,
+		Transaction,
+		H160,
+		H256,
+		U256,
+	},
+};
+
+/// Half the order of the secp256k1 curve, the greatest `s` of a signature allowed by EIP-2.
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+	0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+	0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Checks that `s` is in the lower half of the curve order, as EIP-2 requires, since a signature
+/// with `n - s` recovers the same signer.
+pub(crate) fn is_low_s(s: &U256) -> bool {
+	*s <= U256::from_big_endian(&SECP256K1_HALF_ORDER)
+}
+
+fn append_access_list(stream: &mut RlpStream, access_list: &AccessList) {
+	stream.begin_list(access_list.len());
+	for item in access_list {
+		stream.begin_list(2);
+		stream.append(&item.address);
+		stream.append_list(&item.storage_keys);
+	}
+}
+
+fn append_to(stream: &mut RlpStream, to: &Option<H160>) {
+	match to {
+		Some(to) => stream.append(to),
+		None => stream.append_empty_data(),
+	};
+}
+
+/// Returns the chain id and the recovery id from the `v` of a legacy transaction.
+fn legacy_chain_and_recovery_id(v: u64) -> Result<(Option<u64>, i32), ERC20Error> {
+	match v {
+		27 | 28 => Ok((None, (v - 27) as i32)),
+		v if v >= 35 => Ok((Some((v - 35) / 2), ((v - 35) % 2) as i32)),
+		_ => Err(ERC20Error::InvalidSignature),
+	}
+}
+
+/// Appends `v`, `r`, and `s`, failing if the transaction is not signed.
+fn append_signature(stream: &mut RlpStream, transaction: &Transaction) -> Result<(), ERC20Error> {
+	stream.append(&transaction.v.ok_or(ERC20Error::InvalidSignature)?);
+	stream.append(&transaction.r.ok_or(ERC20Error::InvalidSignature)?);
+	stream.append(&transaction.s.ok_or(ERC20Error::InvalidSignature)?);
+	Ok(())
+}
+
+/// Encodes the transaction, followed by its signature if `signed`, or by the EIP-155 fields if not
+/// signed and `chain_id` is set for a legacy transaction.
+fn encode(transaction: &Transaction, chain_id: Option<u64>, signed: bool) -> Result<Vec<u8>, ERC20Error> {
+	let envelope = TransactionEnvelope::try_from(transaction)?;
+	let signature_items = if signed { 3 } else { 0 };
+	let mut stream = RlpStream::new();
+	match (&envelope.transaction_type, &envelope.gas_pricing) {
+		(TransactionType::Legacy, GasPricing::Legacy { gas_price }) => {
+			stream.begin_list(if signed || chain_id.is_some() { 9 } else { 6 });
+			stream.append(&transaction.nonce);
+			stream.append(gas_price);
+			stream.append(&transaction.gas);
+			append_to(&mut stream, &transaction.to);
+			stream.append(&transaction.value);
+			stream.append(&transaction.input.0);
+			if signed {
+				append_signature(&mut stream, transaction)?;
+			} else if let Some(chain_id) = chain_id {
+				stream.append(&chain_id);
+				stream.append(&0u8);
+				stream.append(&0u8);
+			}
+		}
+		(TransactionType::AccessList, GasPricing::Legacy { gas_price }) => {
+			stream.begin_list(8 + signature_items);
+			stream.append(&chain_id.ok_or(ERC20Error::InvalidSignature)?);
+			stream.append(&transaction.nonce);
+			stream.append(gas_price);
+			stream.append(&transaction.gas);
+			append_to(&mut stream, &transaction.to);
+			stream.append(&transaction.value);
+			stream.append(&transaction.input.0);
+			append_access_list(&mut stream, &envelope.access_list);
+			if signed {
+				append_signature(&mut stream, transaction)?;
+			}
+		}
+		(TransactionType::DynamicFee, GasPricing::DynamicFee { max_fee_per_gas, max_priority_fee_per_gas }) => {
+			stream.begin_list(9 + signature_items);
+			stream.append(&chain_id.ok_or(ERC20Error::InvalidSignature)?);
+			stream.append(&transaction.nonce);
+			stream.append(max_priority_fee_per_gas);
+			stream.append(max_fee_per_gas);
+			stream.append(&transaction.gas);
+			append_to(&mut stream, &transaction.to);
+			stream.append(&transaction.value);
+			stream.append(&transaction.input.0);
+			append_access_list(&mut stream, &envelope.access_list);
+			if signed {
+				append_signature(&mut stream, transaction)?;
+			}
+		}
+		_ => return Err(ERC20Error::UnexpectedType),
+	}
+	let mut resp = Vec::new();
+	if let Some(type_byte) = envelope.transaction_type.type_byte() {
+		resp.push(type_byte);
+	}
+	resp.extend_from_slice(&stream.out());
+	Ok(resp)
+}
+
+/// Returns the bytes signed by the sender, which are hashed to produce the signing hash.
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction.
+/// * `chain_id` - The chain id, required for typed transactions and EIP-155 legacy transactions.
+///
+pub fn signing_payload(transaction: &Transaction, chain_id: Option<u64>) -> Result<Vec<u8>, ERC20Error> {
+	encode(transaction, chain_id, false)
+}
+
+/// Returns the raw bytes of the signed transaction, as broadcast to the network.
+///
+/// # Arguments
+///
+/// * `transaction` - The signed transaction.
+/// * `chain_id` - The chain id, required for typed transactions.
+///
+pub fn signed_payload(transaction: &Transaction, chain_id: Option<u64>) -> Result<Vec<u8>, ERC20Error> {
+	encode(transaction, chain_id, true)
+}
+
+/// Returns the hash signed by the sender.
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction.
+/// * `chain_id` - The chain id, required for typed transactions and EIP-155 legacy transactions.
+///
+pub fn signing_hash(transaction: &Transaction, chain_id: Option<u64>) -> Result<H256, ERC20Error> {
+	Ok(H256(keccak256(&signing_payload(transaction, chain_id)?)))
+}
+
+/// Recovers the sender of a signed transaction from its `v`, `r`, and `s`.
+///
+/// Legacy transactions carry the chain id in `v` (EIP-155), so `chain_id` is only used for typed
+/// transactions, whose `v` is the y-parity of the signature. Signatures with a high `s` are
+/// rejected, as in EIP-2.
+///
+/// # Arguments
+///
+/// * `transaction` - The signed transaction.
+/// * `chain_id` - The chain id, required for typed transactions.
+///
+pub fn recover_sender(transaction: &Transaction, chain_id: Option<u64>) -> Result<H160, ERC20Error> {
+	let v = transaction.v.ok_or(ERC20Error::InvalidSignature)?.as_u64();
+	let r = transaction.r.ok_or(ERC20Error::InvalidSignature)?;
+	let s = transaction.s.ok_or(ERC20Error::InvalidSignature)?;
+	if !is_low_s(&s) {
+		return Err(ERC20Error::InvalidSignature);
+	}
+	let envelope = TransactionEnvelope::try_from(transaction)?;
+	let (chain_id, recovery_id) = match envelope.transaction_type {
+		TransactionType::Legacy => legacy_chain_and_recovery_id(v)?,
+		_ if v <= 1 => (chain_id, v as i32),
+		_ => return Err(ERC20Error::InvalidSignature),
+	};
+	let mut signature = [0u8; 64];
+	r.to_big_endian(&mut signature[..32]);
+	s.to_big_endian(&mut signature[32..]);
+	let hash = signing_hash(transaction, chain_id)?;
+	recover(hash.as_bytes(), &signature, recovery_id).map_err(|_| ERC20Error::InvalidSignature)
+}
+
+/// Returns the chain id a legacy transaction is signed for, taken from its EIP-155 `v`, or `None`
+/// for typed transactions and legacy ones without replay protection.
+///
+/// # Arguments
+///
+/// * `transaction` - The signed transaction.
+///
+pub fn legacy_chain_id(transaction: &Transaction) -> Option<u64> {
+	let envelope = TransactionEnvelope::try_from(transaction).ok()?;
+	match envelope.transaction_type {
+		TransactionType::Legacy => legacy_chain_and_recovery_id(transaction.v?.as_u64()).ok()?.0,
+		_ => None,
+	}
+}
+
+/// Returns the sender of the transaction, recovering it from the signature if it is not set.
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction.
+/// * `chain_id` - The chain id, required to recover the sender of typed transactions.
+///
+pub fn sender(transaction: &Transaction, chain_id: Option<u64>) -> Result<H160, ERC20Error> {
+	match transaction.from {
+		Some(from) => Ok(from),
+		None => recover_sender(transaction, chain_id).map_err(|_| ERC20Error::UnknownSender),
+	}
+}
+
+/// Signs the transaction with a local key, filling its signature, sender, hash, and raw bytes.
+///
+/// Legacy transactions are signed with EIP-155 replay protection.
+///
+/// # Arguments
+///
+/// * `transaction` - The unsigned transaction.
+/// * `chain_id` - The chain id.
+/// * `key` - The sender private key, e.g. a `web3::signing::SecretKeyRef`.
+///
+pub fn sign<K: Key>(mut transaction: Transaction, chain_id: u64, key: K) -> Result<SignedTransaction, ERC20Error> {
+	let envelope = TransactionEnvelope::try_from(&transaction)?;
+	let hash = signing_hash(&transaction, Some(chain_id))?;
+	let signature = key.sign_message(hash.as_bytes()).map_err(|_| ERC20Error::InvalidSignature)?;
+	let v = match envelope.transaction_type {
+		TransactionType::Legacy => chain_id.checked_mul(2)
+			.and_then(|it| it.checked_add(35 + signature.v))
+			.ok_or(ERC20Error::InvalidSignature)?,
+		_ => signature.v,
+	};
+	transaction.v = Some(v.into());
+	transaction.r = Some(U256::from_big_endian(signature.r.as_bytes()));
+	transaction.s = Some(U256::from_big_endian(signature.s.as_bytes()));
+	let raw = signed_payload(&transaction, Some(chain_id))?;
+	transaction.hash = H256(keccak256(&raw));
+	transaction.raw = Some(Bytes(raw));
+	transaction.from = Some(key.address());
+	Ok(SignedTransaction {
+		transaction,
+		chain_id: Some(chain_id),
+	})
+}
@@ -0,0 +1,189 @@
+use crate::{
+	classifier::TransactionClassifier,
+	erc20::ERC20Call,
+	raw::SignedTransaction,
+	signature::{
+		legacy_chain_id,
+		recover_sender,
+		sender,
+		sign,
+		signed_payload,
+		signing_hash,
+		signing_payload,
+	},
+	test_fixtures::{
+		eip155_sender,
+		EIP155_RAW,
+	},
+	transaction::TransactionAndTransferType,
+	transfer::Transfer,
+	ERC20Error,
+};
+use secp256k1::SecretKey;
+use std::{
+	convert::{
+		TryFrom,
+		TryInto,
+	},
+	str::FromStr,
+};
+use web3::{
+	signing::{
+		Key,
+		SecretKeyRef,
+	},
+	types::{
+		AccessListItem,
+		Transaction,
+		H160,
+		H256,
+		U256,
+	},
+};
+
+fn typed_transaction(transaction_type: u64) -> Transaction {
+	Transaction {
+		nonce: 3.into(),
+		gas: 60_000.into(),
+		to: Some(H160::from_low_u64_be(0x70c3e)),
+		input: ERC20Call::Transfer { to: H160::from_low_u64_be(1), value: 10.into() }.encode(),
+		transaction_type: Some(transaction_type.into()),
+		gas_price: Some(20.into()),
+		max_fee_per_gas: Some(100.into()),
+		max_priority_fee_per_gas: Some(2.into()),
+		access_list: Some(vec![AccessListItem {
+			address: H160::from_low_u64_be(0x70c3e),
+			storage_keys: vec![H256::from_low_u64_be(7)],
+		}]),
+		..Default::default()
+	}
+}
+
+fn sign_in_place(transaction: &mut Transaction, chain_id: u64) -> H160 {
+	let key = SecretKey::from_slice(&[0x46; 32]).unwrap();
+	let key = SecretKeyRef::new(&key);
+	let hash = signing_hash(transaction, Some(chain_id)).unwrap();
+	let signature = key.sign_message(hash.as_bytes()).unwrap();
+	transaction.v = Some(signature.v.into());
+	transaction.r = Some(U256::from_big_endian(signature.r.as_bytes()));
+	transaction.s = Some(U256::from_big_endian(signature.s.as_bytes()));
+	key.address()
+}
+
+#[test]
+fn eip155_signing_hash() {
+	let signed = SignedTransaction::try_from(hex::decode(EIP155_RAW).unwrap().as_slice()).unwrap();
+	assert_eq!(
+		"ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080",
+		hex::encode(signing_payload(&signed.transaction, Some(1)).unwrap()),
+	);
+	assert_eq!(
+		H256::from_str("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53").unwrap(),
+		signing_hash(&signed.transaction, Some(1)).unwrap(),
+	);
+}
+
+#[test]
+fn recover_legacy_sender() {
+	let signed = SignedTransaction::try_from(hex::decode(EIP155_RAW).unwrap().as_slice()).unwrap();
+	assert_eq!(Some(eip155_sender()), signed.transaction.from);
+
+	let mut transaction = signed.transaction;
+	transaction.from = None;
+	assert_eq!(Ok(eip155_sender()), recover_sender(&transaction, None));
+
+	assert_eq!(Some(1), legacy_chain_id(&transaction));
+
+	// The sender is recovered when creating the transfer.
+	let transfer: TransactionAndTransferType = transaction.clone().try_into().unwrap();
+	assert_eq!(eip155_sender(), transfer.from());
+	assert_eq!(Some(1), transfer.chain_id());
+
+	// It is signed for mainnet, so it cannot be taken from another chain.
+	let parsed = TransactionClassifier::new().with_chain_id(5).classify(transaction);
+	assert_eq!(Err(ERC20Error::ChainMismatch), TransactionAndTransferType::try_from(parsed));
+}
+
+#[test]
+fn reject_high_s() {
+	let signed = SignedTransaction::try_from(hex::decode(EIP155_RAW).unwrap().as_slice()).unwrap();
+	let mut transaction = signed.transaction;
+	transaction.from = None;
+
+	// `n - s` with the other y-parity is the same signature for secp256k1, but not for EIP-2.
+	let order = U256::from_str("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+	transaction.s = Some(order - transaction.s.unwrap());
+	transaction.v = Some(38.into());
+	assert_eq!(Err(ERC20Error::InvalidSignature), recover_sender(&transaction, None));
+	assert_eq!(Err(ERC20Error::UnknownSender), sender(&transaction, None));
+}
+
+#[test]
+fn recover_typed_sender() {
+	for transaction_type in [1, 2].iter() {
+		let mut transaction = typed_transaction(*transaction_type);
+		let address = sign_in_place(&mut transaction, 5);
+		assert_eq!(Ok(address), recover_sender(&transaction, Some(5)));
+		assert_eq!(Ok(address), sender(&transaction, Some(5)));
+		// The y-parity form does not carry the chain id.
+		assert_eq!(Err(ERC20Error::InvalidSignature), recover_sender(&transaction, None));
+		assert_ne!(Ok(address), recover_sender(&transaction, Some(1)));
+	}
+}
+
+#[test]
+fn unknown_sender() {
+	let mut transaction = typed_transaction(2);
+	assert_eq!(Err(ERC20Error::InvalidSignature), recover_sender(&transaction, Some(1)));
+	assert_eq!(Err(ERC20Error::UnknownSender), sender(&transaction, Some(1)));
+
+	transaction.v = Some(5.into());
+	transaction.r = Some(1.into());
+	transaction.s = Some(1.into());
+	assert_eq!(Err(ERC20Error::InvalidSignature), recover_sender(&transaction, Some(1)));
+
+	// Typed transactions are recovered with the chain id of the parsed transaction, which has to be
+	// known.
+	let address = sign_in_place(&mut transaction, 1);
+	let resp: Result<TransactionAndTransferType, _> = transaction.clone().try_into();
+	assert_eq!(Err(ERC20Error::UnknownSender), resp);
+	assert_eq!(None, legacy_chain_id(&transaction));
+
+	let parsed = TransactionClassifier::new().with_chain_id(1).classify(transaction);
+	let resp: TransactionAndTransferType = parsed.try_into().unwrap();
+	assert_eq!(address, resp.from());
+}
+
+#[test]
+fn sign_eip155() {
+	let raw = hex::decode(EIP155_RAW).unwrap();
+	let transaction = Transaction {
+		nonce: 9.into(),
+		gas_price: Some(20_000_000_000u64.into()),
+		gas: 21_000.into(),
+		to: Some(H160::from_str("3535353535353535353535353535353535353535").unwrap()),
+		value: U256::from_dec_str("1000000000000000000").unwrap(),
+		..Default::default()
+	};
+	let key = SecretKey::from_slice(&[0x46; 32]).unwrap();
+	let signed = sign(transaction, 1, SecretKeyRef::new(&key)).unwrap();
+	assert_eq!(Some(1), signed.chain_id);
+	assert_eq!(Some(raw.clone().into()), signed.transaction.raw);
+	assert_eq!(raw, signed_payload(&signed.transaction, signed.chain_id).unwrap());
+	assert_eq!(SignedTransaction::try_from(raw.as_slice()).unwrap(), signed);
+}
+
+#[test]
+fn sign_typed() {
+	let key = SecretKey::from_slice(&[0x46; 32]).unwrap();
+	for transaction_type in [1, 2].iter() {
+		let signed = sign(typed_transaction(*transaction_type), 5, SecretKeyRef::new(&key)).unwrap();
+		let raw = signed.transaction.raw.clone().unwrap();
+		assert_eq!(Some(*transaction_type as u8), raw.0.first().cloned());
+		let parsed = SignedTransaction::try_from(raw).unwrap();
+		assert_eq!(signed.transaction.hash, parsed.transaction.hash);
+		assert_eq!(signed.transaction.from, parsed.transaction.from);
+		assert_eq!(signed.transaction.v, parsed.transaction.v);
+	}
+	assert_eq!(Err(ERC20Error::InvalidSignature), signed_payload(&typed_transaction(2), Some(5)));
+}
@@ -0,0 +1,10 @@
+use std::str::FromStr;
+use web3::types::H160;
+
+// EIP-155 example, signed with the private key 0x4646...46 for chain id 1.
+pub(crate) const EIP155_RAW: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+/// Returns the sender of `EIP155_RAW`.
+pub(crate) fn eip155_sender() -> H160 {
+	H160::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap()
+}
@@ -0,0 +1,185 @@
+//! Token lists import, following the tokenlists.org schema.
+
+use crate::{
+	registry::{
+		TokenInfo,
+		TokenRegistry,
+	},
+	ERC20Error,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use serde_json::Value;
+use std::{
+	collections::{
+		BTreeMap,
+		HashSet,
+	},
+	fmt,
+};
+use web3::types::H160;
+
+const MAX_TOKENS: usize = 10_000;
+const MAX_LIST_NAME_SIZE: usize = 30;
+const MAX_TOKEN_NAME_SIZE: usize = 40;
+const MAX_SYMBOL_SIZE: usize = 20;
+
+/// Semantic version of a token list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Version {
+	/// Incremented when tokens are removed.
+	pub major: u32,
+	/// Incremented when tokens are added.
+	pub minor: u32,
+	/// Incremented when the tokens details change.
+	pub patch: u32,
+}
+
+impl fmt::Display for Version {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+	}
+}
+
+/// Tag definition of a token list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Tag {
+	/// The tag name.
+	pub name: String,
+	/// What the tag means for the tokens it applies to.
+	pub description: String,
+}
+
+/// Token of a token list.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenListEntry {
+	/// The chain id the token is deployed on.
+	pub chain_id: u64,
+	/// The token contract address.
+	pub address: H160,
+	/// The token symbol.
+	pub symbol: String,
+	/// The token name.
+	pub name: String,
+	/// The number of decimals of the token amounts.
+	pub decimals: u8,
+	/// The tags of the list applying to the token.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub tags: Vec<String>,
+	/// The token logo.
+	#[serde(default, rename = "logoURI", skip_serializing_if = "Option::is_none")]
+	pub logo_uri: Option<String>,
+	/// Extra details of the token, e.g. its bridged addresses.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub extensions: BTreeMap<String, Value>,
+}
+
+impl From<&TokenListEntry> for TokenInfo {
+	fn from(entry: &TokenListEntry) -> Self {
+		Self {
+			chain_id: entry.chain_id,
+			address: entry.address,
+			symbol: entry.symbol.clone(),
+			name: Some(entry.name.clone()),
+			decimals: Some(entry.decimals),
+		}
+	}
+}
+
+/// Token list, as published on tokenlists.org, deserialized from its JSON file.
+///
+/// ```
+/// use erc20::{
+///     chain,
+///     registry::TokenRegistry,
+///     tokenlist::TokenList,
+/// };
+///
+/// let list: TokenList = serde_json::from_str(r#"{
+///     "name": "Stablecoins",
+///     "timestamp": "2021-01-01T00:00:00.000Z",
+///     "version": { "major": 1, "minor": 2, "patch": 0 },
+///     "tags": { "stablecoin": { "name": "Stablecoin", "description": "Pegged to a fiat currency" } },
+///     "tokens": [{
+///         "chainId": 1,
+///         "address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+///         "symbol": "USDC",
+///         "name": "USD Coin",
+///         "decimals": 6,
+///         "tags": ["stablecoin"]
+///     }]
+/// }"#).unwrap();
+/// assert_eq!("1.2.0", list.version.to_string());
+///
+/// let registry = list.registry().unwrap();
+/// assert_eq!(Some(6), registry.by_symbol(chain::MAINNET, "USDC").unwrap().decimals);
+///
+/// // The list tokens are added to the built-in ones.
+/// let mut registry = TokenRegistry::builtin();
+/// registry.extend(list.registry().unwrap().tokens().cloned());
+/// assert_eq!(Some("USD Coin".to_string()), registry.by_symbol(chain::MAINNET, "USDC").unwrap().name);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenList {
+	/// The list name.
+	pub name: String,
+	/// When the list was published.
+	pub timestamp: String,
+	/// The list version.
+	pub version: Version,
+	/// The tokens.
+	pub tokens: Vec<TokenListEntry>,
+	/// The list keywords.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub keywords: Vec<String>,
+	/// The tags applying to the tokens, by their identifier.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub tags: BTreeMap<String, Tag>,
+	/// The list logo.
+	#[serde(default, rename = "logoURI", skip_serializing_if = "Option::is_none")]
+	pub logo_uri: Option<String>,
+}
+
+impl TokenList {
+	/// Checks the list follows the schema, that the tokens only use the tags defined by the list,
+	/// and that no address is listed twice on the same chain.
+	pub fn validate(&self) -> Result<(), ERC20Error> {
+		if !is_valid_text(&self.name, MAX_LIST_NAME_SIZE) || self.tokens.is_empty() || self.tokens.len() > MAX_TOKENS {
+			return Err(ERC20Error::InvalidTokenList);
+		}
+		let mut addresses = HashSet::new();
+		for token in &self.tokens {
+			let valid_symbol = is_valid_text(&token.symbol, MAX_SYMBOL_SIZE)
+				&& !token.symbol.chars().any(char::is_whitespace);
+			if !valid_symbol || !is_valid_text(&token.name, MAX_TOKEN_NAME_SIZE) {
+				return Err(ERC20Error::InvalidTokenList);
+			}
+			if token.tags.iter().any(|tag| !self.tags.contains_key(tag)) {
+				return Err(ERC20Error::InvalidTokenList);
+			}
+			if !addresses.insert((token.chain_id, token.address)) {
+				return Err(ERC20Error::DuplicateToken);
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the registry with the tokens of every chain, after validating the list.
+	pub fn registry(&self) -> Result<TokenRegistry, ERC20Error> {
+		self.validate()?;
+		let tokens = self.tokens.iter()
+			.map(TokenInfo::from)
+			.collect::<Vec<TokenInfo>>();
+		Ok(tokens.into())
+	}
+}
+
+fn is_valid_text(value: &str, max_size: usize) -> bool {
+	let size = value.chars().count();
+	size > 0 && size <= max_size && value.trim() == value
+}
@@ -0,0 +1,159 @@
+use crate::{
+	chain,
+	tokenlist::{
+		Tag,
+		TokenList,
+		TokenListEntry,
+		Version,
+	},
+	ERC20Error,
+};
+use serde_json::Value;
+use std::{
+	collections::BTreeMap,
+	str::FromStr,
+};
+use web3::types::H160;
+
+const TOKEN_LIST: &str = r#"{
+	"name": "Curated",
+	"timestamp": "2021-05-01T00:00:00.000Z",
+	"version": { "major": 2, "minor": 1, "patch": 3 },
+	"keywords": ["curated"],
+	"logoURI": "ipfs://list",
+	"tags": { "stablecoin": { "name": "Stablecoin", "description": "Pegged to a fiat currency" } },
+	"tokens": [
+		{
+			"chainId": 1,
+			"address": "0x6B175474E89094C44Da98b954EedeAC495271d0F",
+			"symbol": "DAI",
+			"name": "Dai Stablecoin",
+			"decimals": 18,
+			"logoURI": "ipfs://dai",
+			"tags": ["stablecoin"]
+		},
+		{
+			"chainId": 1,
+			"address": "0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599",
+			"symbol": "WBTC",
+			"name": "Wrapped BTC",
+			"decimals": 8
+		},
+		{
+			"chainId": 137,
+			"address": "0x8f3Cf7ad23Cd3CaDbD9735AFf958023239c6A063",
+			"symbol": "DAI",
+			"name": "(PoS) Dai Stablecoin",
+			"decimals": 18,
+			"extensions": { "bridgeInfo": {} }
+		}
+	]
+}"#;
+
+fn token_list() -> TokenList {
+	serde_json::from_str(TOKEN_LIST).unwrap()
+}
+
+fn entry(chain_id: u64, address: u64, symbol: &str) -> TokenListEntry {
+	TokenListEntry {
+		chain_id,
+		address: H160::from_low_u64_be(address),
+		symbol: symbol.to_string(),
+		name: symbol.to_string(),
+		decimals: 18,
+		tags: Vec::new(),
+		logo_uri: None,
+		extensions: BTreeMap::new(),
+	}
+}
+
+#[test]
+fn parse_token_list() {
+	let list = token_list();
+	assert_eq!("Curated", list.name);
+	assert_eq!(Version { major: 2, minor: 1, patch: 3 }, list.version);
+	assert_eq!("2.1.3", list.version.to_string());
+	assert!(Version { major: 2, minor: 0, patch: 9 } < list.version);
+	assert_eq!(3, list.tokens.len());
+	assert_eq!(vec!["stablecoin".to_string()], list.tokens[0].tags);
+	assert_eq!(Some("ipfs://dai".to_string()), list.tokens[0].logo_uri);
+	assert_eq!(
+		Some(&Tag { name: "Stablecoin".to_string(), description: "Pegged to a fiat currency".to_string() }),
+		list.tags.get("stablecoin"),
+	);
+	assert_eq!(Some(&serde_json::json!({})), list.tokens[2].extensions.get("bridgeInfo"));
+	assert_eq!(Ok(()), list.validate());
+}
+
+#[test]
+fn token_list_round_trip() {
+	// Addresses are written back in lower case, without their checksum.
+	let mut json: Value = serde_json::from_str(TOKEN_LIST).unwrap();
+	for token in json["tokens"].as_array_mut().unwrap() {
+		token["address"] = token["address"].as_str().unwrap().to_lowercase().into();
+	}
+	assert_eq!(json, serde_json::to_value(token_list()).unwrap());
+}
+
+#[test]
+fn import_chain_tokens() {
+	let registry = token_list().registry().unwrap();
+	assert_eq!(3, registry.len());
+	assert_eq!(2, registry.chain_tokens(chain::MAINNET).count());
+	let dai = registry.by_symbol(chain::MAINNET, "DAI").unwrap();
+	assert_eq!(H160::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap(), dai.address);
+	assert_eq!(Some("Dai Stablecoin".to_string()), dai.name);
+	assert_eq!(Some(18), dai.decimals);
+	assert_eq!(Some(8), registry.by_symbol(chain::MAINNET, "WBTC").unwrap().decimals);
+
+	let dai = registry.by_symbol(chain::POLYGON, "DAI").unwrap();
+	assert_eq!(chain::POLYGON, dai.chain_id);
+	assert_eq!(Some("(PoS) Dai Stablecoin".to_string()), dai.name);
+
+	assert_eq!(0, registry.chain_tokens(chain::OPTIMISM).count());
+}
+
+#[test]
+fn duplicate_addresses() {
+	let mut list = token_list();
+	list.tokens.push(entry(1, 1, "ONE"));
+	assert_eq!(Ok(()), list.validate());
+
+	// The same address on another chain is a different token.
+	list.tokens.push(entry(5, 1, "ONE"));
+	assert_eq!(Ok(()), list.validate());
+
+	list.tokens.push(entry(1, 1, "UNO"));
+	assert_eq!(Err(ERC20Error::DuplicateToken), list.validate());
+	assert_eq!(Err(ERC20Error::DuplicateToken), list.registry());
+}
+
+#[test]
+fn invalid_token_lists() {
+	let invalid_lists = vec![
+		TokenList { name: String::new(), ..token_list() },
+		TokenList { name: "A name longer than thirty characters".to_string(), ..token_list() },
+		TokenList { tokens: Vec::new(), ..token_list() },
+		TokenList { tokens: vec![entry(1, 1, "TWO WORDS")], ..token_list() },
+		TokenList { tokens: vec![entry(1, 1, "")], ..token_list() },
+		TokenList { tokens: vec![entry(1, 1, "ASYMBOLLONGERTHAN20CHARS")], ..token_list() },
+		TokenList { tags: BTreeMap::new(), ..token_list() },
+	];
+	for list in invalid_lists {
+		assert_eq!(Err(ERC20Error::InvalidTokenList), list.validate(), "{:?}", list);
+	}
+}
+
+#[test]
+fn missing_required_fields() {
+	for field in ["name", "timestamp", "version", "tokens"].iter() {
+		let mut json: Value = serde_json::from_str(TOKEN_LIST).unwrap();
+		json.as_object_mut().unwrap().remove(*field);
+		assert!(serde_json::from_value::<TokenList>(json).is_err(), "{}", field);
+	}
+	for field in ["chainId", "address", "symbol", "name", "decimals"].iter() {
+		let mut json: Value = serde_json::from_str(TOKEN_LIST).unwrap();
+		json["tokens"][0].as_object_mut().unwrap().remove(*field);
+		assert!(serde_json::from_value::<TokenList>(json).is_err(), "{}", field);
+	}
+}
@@ -1,19 +1,31 @@
-///! web3 transaction specific operations.
+//! web3 transaction specific operations.
 
 use crate::{
-	erc20::ERC20Method,
+	classifier::TransactionClassifier,
+	envelope::TransactionEnvelope,
+	signature,
+	erc20::{
+		Approval,
+		ContractAddress,
+		ERC20Call,
+		ERC20Method,
+		PermitApproval,
+	},
 	error::ERC20Error,
 	transfer::{
+		TransferStatus,
 		TransferType,
 		Transfer,
 	},
-	util::BytesToFixedNumber,
 };
 use serde::{
 	Deserialize,
 	Serialize,
 };
-use std::convert::TryFrom;
+use std::convert::{
+	TryFrom,
+	TryInto,
+};
 use web3::types::{
 	H160,
 	H256,
@@ -24,35 +36,66 @@ use web3::types::{
 };
 
 /// Identifies an Ethereum transaction as a transfer, contract invocation, creation, or other.
+///
+/// Every variant starts with the chain id of the network the transaction belongs to, so the
+/// addresses it refers to are not taken for the ones of another network. It is `None` when the
+/// network is unknown, e.g. for typed transactions fetched without their chain.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ParsedTransaction {
-	/// Ether transfer transaction.
-	EthereumTransfer(Transaction),
+	/// Ether transfer transaction, the input may carry a memo if the recipient is known to be an
+	/// externally owned account.
+	EthereumTransfer(Option<u64>, Transaction),
+	/// Ether transfer transaction where the sender is also the recipient.
+	SelfTransfer(Option<u64>, Transaction),
+	/// Transaction with no value and no contract invocation, e.g. a ping or a nonce cancellation.
+	ZeroValuePing(Option<u64>, Transaction),
 	/// Smart contract invocation transaction.
-	ContractInvocation(TransactionContractInvocation),
+	ContractInvocation(Option<u64>, TransactionContractInvocation),
 	/// Smart contract creation transaction.
-	ContractCreation(Transaction),
+	ContractCreation(Option<u64>, Transaction),
 	/// Unidentified transaction.
-	Other(Transaction),
+	Other(Option<u64>, Transaction),
 }
 
+/// Transaction of an unknown chain, but for legacy ones signed with EIP-155 replay protection, use
+/// `TransactionClassifier::with_chain_id` when the chain is known.
 impl From<Transaction> for ParsedTransaction {
 	#[inline]
 	fn from(transaction: Transaction) -> Self {
-		match transaction.to {
-			None => if transaction.input.0.is_empty() {
-				Self::Other(transaction)
-			} else {
-				Self::ContractCreation(transaction)
-			},
-			Some(_) => if !transaction.input.0.is_empty() {
-				Self::ContractInvocation(transaction.into())
-			} else {
-				Self::Other(transaction)
-			},
+		TransactionClassifier::new().classify(transaction)
+	}
+}
+
+impl ParsedTransaction {
+	/// Returns the parsed transaction.
+	pub fn transaction(&self) -> &Transaction {
+		match self {
+			Self::EthereumTransfer(_, transaction) => transaction,
+			Self::SelfTransfer(_, transaction) => transaction,
+			Self::ZeroValuePing(_, transaction) => transaction,
+			Self::ContractInvocation(_, contract_invocation) => contract_invocation.transaction(),
+			Self::ContractCreation(_, transaction) => transaction,
+			Self::Other(_, transaction) => transaction,
+		}
+	}
+
+	/// Returns the chain id of the transaction, if known.
+	pub fn chain_id(&self) -> Option<u64> {
+		match self {
+			Self::EthereumTransfer(chain_id, _)
+			| Self::SelfTransfer(chain_id, _)
+			| Self::ZeroValuePing(chain_id, _)
+			| Self::ContractInvocation(chain_id, _)
+			| Self::ContractCreation(chain_id, _)
+			| Self::Other(chain_id, _) => *chain_id,
 		}
 	}
+
+	/// Returns the EIP-2718 type, gas pricing, and access list of the transaction.
+	pub fn envelope(&self) -> Result<TransactionEnvelope, ERC20Error> {
+		TransactionEnvelope::try_from(self.transaction())
+	}
 }
 
 impl Default for ParsedTransaction {
@@ -80,6 +123,54 @@ impl From<Transaction> for TransactionContractInvocation {
 	}
 }
 
+impl TransactionContractInvocation {
+	/// Returns the invoked transaction.
+	pub fn transaction(&self) -> &Transaction {
+		match self {
+			Self::ERC20(_, transaction) => transaction,
+			Self::Other(transaction) => transaction,
+		}
+	}
+
+	/// Decodes the ERC20 method arguments, including the ones from read calls as `balanceOf` and
+	/// `allowance`.
+	pub fn erc20_call(&self) -> Result<ERC20Call, ERC20Error> {
+		match self {
+			Self::ERC20(_, transaction) => ERC20Call::decode(&transaction.input.0),
+			Self::Other(_) => Err(ERC20Error::UnexpectedType),
+		}
+	}
+
+	/// Returns the approval for an `approve` invocation, the owner being the transaction sender, or
+	/// for a `permit` invocation, the owner being the permit signer.
+	pub fn approval(&self) -> Result<Approval, ERC20Error> {
+		match self {
+			Self::ERC20(ERC20Method::Approve, transaction) => match self.erc20_call()? {
+				ERC20Call::Approve { spender, value } => Ok(Approval {
+					owner: transaction.from.ok_or(ERC20Error::UnknownSender)?,
+					spender,
+					value,
+				}),
+				_ => Err(ERC20Error::NoApprovalTransaction),
+			},
+			Self::ERC20(ERC20Method::Permit, _) | Self::ERC20(ERC20Method::DaiPermit, _) => {
+				Ok(self.permit_approval()?.approval)
+			}
+			_ => Err(ERC20Error::NoApprovalTransaction),
+		}
+	}
+
+	/// Returns the approval with its deadline for a `permit` invocation.
+	pub fn permit_approval(&self) -> Result<PermitApproval, ERC20Error> {
+		match self {
+			Self::ERC20(ERC20Method::Permit, _) | Self::ERC20(ERC20Method::DaiPermit, _) => {
+				self.erc20_call()?.permit_approval()
+			}
+			_ => Err(ERC20Error::NoApprovalTransaction),
+		}
+	}
+}
+
 /// Transaction and transaction type information for asset transfers.
 ///
 /// ```
@@ -105,13 +196,14 @@ impl From<Transaction> for TransactionContractInvocation {
 ///     block_hash: None,
 ///     block_number: None,
 ///     transaction_index: None,
-///     from: H160::random(),
+///     from: Some(H160::random()),
 ///     to: Some(H160::random()),
 ///     value: Default::default(),
 ///     gas_price: Default::default(),
 ///     gas: Default::default(),
 ///     input: Bytes(hex::decode(serialized_str).unwrap()),
 ///     raw: None,
+///     ..Default::default()
 /// };
 ///
 /// let resp: Result<TransactionAndTransferType, ERC20Error> = transaction.clone().try_into();
@@ -119,8 +211,14 @@ impl From<Transaction> for TransactionContractInvocation {
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionAndTransferType {
+	chain_id: Option<u64>,
 	transaction: Transaction,
 	transfer_type: TransferType,
+	status: TransferStatus,
+	from: H160,
+	to: H160,
+	contract: Option<H160>,
+	value: U256,
 }
 
 impl TryFrom<Transaction> for TransactionAndTransferType {
@@ -128,128 +226,148 @@ impl TryFrom<Transaction> for TransactionAndTransferType {
 
 	fn try_from(value: Transaction) -> Result<Self, Self::Error> {
 		let parsed_transaction: ParsedTransaction = value.into();
+		parsed_transaction.try_into()
+	}
+}
+
+impl TryFrom<ParsedTransaction> for TransactionAndTransferType {
+	type Error = ERC20Error;
+
+	fn try_from(parsed_transaction: ParsedTransaction) -> Result<Self, Self::Error> {
 		match parsed_transaction {
-			ParsedTransaction::EthereumTransfer(transaction) => Ok(Self {
-				transaction,
-				transfer_type: TransferType::Ethereum,
-			}),
-			ParsedTransaction::ContractInvocation(transaction) => {
+			ParsedTransaction::EthereumTransfer(chain_id, transaction)
+			| ParsedTransaction::SelfTransfer(chain_id, transaction) => {
+				Self::new(chain_id, transaction, TransferType::Ethereum)
+			}
+			ParsedTransaction::ContractInvocation(chain_id, transaction) => {
 				let contract_invocation: TransactionContractInvocation = transaction;
 				match contract_invocation {
 					TransactionContractInvocation::ERC20(method, transaction) => {
 						match method {
-							ERC20Method::Transfer => Ok(Self {
-								transaction,
-								transfer_type: TransferType::ERC20,
-							}),
-							ERC20Method::TransferFrom => Ok(Self {
-								transaction,
-								transfer_type: TransferType::ERC20,
-							}),
+							ERC20Method::Transfer
+							| ERC20Method::TransferFrom
+							| ERC20Method::Mint
+							| ERC20Method::Burn
+							| ERC20Method::BurnFrom => Self::new(chain_id, transaction, TransferType::ERC20),
 							_ => Err(ERC20Error::NoTransferTransaction),
 						}
 					}
 					TransactionContractInvocation::Other(_) => Err(ERC20Error::NoTransferTransaction),
 				}
 			}
-			ParsedTransaction::ContractCreation(_) => Err(ERC20Error::NoTransferTransaction),
-			ParsedTransaction::Other(_) => Err(ERC20Error::NoTransferTransaction),
+			ParsedTransaction::ZeroValuePing(_, _) => Err(ERC20Error::NoTransferTransaction),
+			ParsedTransaction::ContractCreation(_, _) => Err(ERC20Error::NoTransferTransaction),
+			ParsedTransaction::Other(_, _) => Err(ERC20Error::NoTransferTransaction),
 		}
 	}
 }
 
 impl TransactionAndTransferType {
+	/// Decodes the transfer once, so any error surfaces here and the `Transfer` accessors cannot fail.
+	/// When the sender is not set, it is recovered from the signature, which fails for typed
+	/// transactions of an unknown chain. A legacy transaction signed for another chain is rejected.
+	fn new(
+		chain_id: Option<u64>,
+		transaction: Transaction,
+		transfer_type: TransferType,
+	) -> Result<Self, ERC20Error> {
+		match (chain_id, signature::legacy_chain_id(&transaction)) {
+			(Some(chain_id), Some(signed_chain_id)) if chain_id != signed_chain_id => {
+				return Err(ERC20Error::ChainMismatch)
+			}
+			_ => {}
+		}
+		let recipient = transaction.to.ok_or(ERC20Error::NoTransferTransaction)?;
+		let sender = signature::sender(&transaction, chain_id);
+		let (from, to, contract, value) = match transfer_type {
+			TransferType::Ethereum => (sender?, recipient, None, transaction.value),
+			TransferType::ERC20 => match ERC20Call::decode(&transaction.input.0)? {
+				ERC20Call::Transfer { to, value } => (sender?, to, Some(recipient), value),
+				ERC20Call::TransferFrom { from, to, value } => (from, to, Some(recipient), value),
+				// Mints and burns are transfers from and to the zero address, as in their `Transfer` logs.
+				ERC20Call::Mint { to, value } => (H160::zero(), to, Some(recipient), value),
+				ERC20Call::Burn { value } => (sender?, H160::zero(), Some(recipient), value),
+				ERC20Call::BurnFrom { from, value } => (from, H160::zero(), Some(recipient), value),
+				_ => return Err(ERC20Error::NoTransferTransaction),
+			},
+		};
+		Ok(Self {
+			chain_id,
+			transaction,
+			transfer_type,
+			status: TransferStatus::Unknown,
+			from,
+			to,
+			contract,
+			value,
+		})
+	}
+
+	/// Sets the execution status, known from the transaction receipt.
+	pub(crate) fn with_status(mut self, status: TransferStatus) -> Self {
+		self.status = status;
+		self
+	}
+
+	/// Returns the transaction.
+	pub fn transaction(&self) -> &Transaction {
+		&self.transaction
+	}
+
+	/// Returns the kind of transfer.
+	pub fn transfer_type(&self) -> TransferType {
+		self.transfer_type.clone()
+	}
+
+	/// Returns the ERC20 contract, identified on the chain of the transaction, which is unidentified
+	/// if the chain is unknown.
+	pub fn contract_address(&self) -> Option<ContractAddress> {
+		self.contract.map(|contract| match self.chain_id {
+			Some(chain_id) => ContractAddress::from_address(chain_id, contract),
+			None => ContractAddress::Unidentified(contract),
+		})
+	}
+
+	/// Returns the EIP-2718 type, gas pricing, and access list of the transaction.
+	pub fn envelope(&self) -> Result<TransactionEnvelope, ERC20Error> {
+		TransactionEnvelope::try_from(&self.transaction)
+	}
+
+	/// Returns the storage keys of the ERC20 contract pre-warmed by the access list.
+	pub fn contract_storage_keys(&self) -> Result<Vec<H256>, ERC20Error> {
+		match self.contract {
+			Some(contract) => Ok(self.envelope()?.storage_keys(&contract)),
+			None => Ok(Vec::new()),
+		}
+	}
+
 	/// Gets information from the transaction.
 	/// The `from`, `to`, and `value` regardless if it is an ERC20 or Ether transfer.
+	/// They are decoded when the transfer is created, so it does not fail.
 	pub fn get_from_to_value(&self) -> Result<(H160, H160, U256), ERC20Error> {
-		let from_v: H160;
-		let to_v: H160;
-		let value_v: U256;
-		match self.transfer_type {
-			TransferType::Ethereum => {
-				from_v = self.transaction.from.unwrap();
-				if let Some(v) = self.transaction.to {
-					to_v = v;
-				} else {
-					return Err(ERC20Error::NoTransferTransaction);
-				}
-				value_v = self.transaction.value;
-			}
-			TransferType::ERC20 => {
-				let contract_invocation: TransactionContractInvocation =
-					self.transaction.clone().into();
-				match contract_invocation {
-					TransactionContractInvocation::ERC20(method, transaction) => {
-						match method {
-							ERC20Method::Transfer => {
-								let mut resp: BytesToFixedNumber =
-									transaction.input.clone().into();
-								let _ignore = resp.next_vec(4);
-								from_v = transaction.from.unwrap();
-								to_v = resp.next_h160()?;
-								value_v = resp.next_u256()?;
-							}
-							ERC20Method::TransferFrom => {
-								let mut resp: BytesToFixedNumber =
-									transaction.input.into();
-								let _ignore = resp.next_vec(4);
-								from_v = resp.next_h160()?;
-								to_v = resp.next_h160()?;
-								value_v = resp.next_u256()?;
-							}
-							_ => {
-								return Err(ERC20Error::NoTransferTransaction);
-							}
-						}
-					}
-					TransactionContractInvocation::Other(_) => {
-						return Err(ERC20Error::NoTransferTransaction);
-					}
-				}
-			}
-		}
-		Ok((from_v, to_v, value_v))
+		Ok((self.from, self.to, self.value))
 	}
 }
 
 impl Transfer for TransactionAndTransferType {
+	fn chain_id(&self) -> Option<u64> {
+		self.chain_id
+	}
+
 	fn from(&self) -> H160 {
-		match self.get_from_to_value() {
-			Ok((the_from, _, _)) => the_from,
-			Err(err) => {
-				panic!("Unexpected transaction for TransactionAndTransferType::from {:?}", err)
-			}
-		}
+		self.from
 	}
 
 	fn to(&self) -> H160 {
-		match self.get_from_to_value() {
-			Ok((_, the_to, _)) => the_to,
-			Err(err) => {
-				panic!("Unexpected transaction for TransactionAndTransferType::to {:?}", err)
-			}
-		}
+		self.to
 	}
 
 	fn contract(&self) -> Option<H160> {
-		match self.transfer_type {
-			TransferType::Ethereum => None,
-			TransferType::ERC20 => {
-				match self.transaction.to {
-					None => panic!("Unexpected transaction for TransactionAndTransferType::contract"),
-					Some(to) => Some(to),
-				}
-			}
-		}
+		self.contract
 	}
 
 	fn value(&self) -> U256 {
-		match self.get_from_to_value() {
-			Ok((_, _, the_value)) => the_value,
-			Err(err) => {
-				panic!("Unexpected transaction for TransactionAndTransferType::value {:?}", err)
-			}
-		}
+		self.value
 	}
 
 	fn tx_hash(&self) -> H256 {
@@ -267,4 +385,8 @@ impl Transfer for TransactionAndTransferType {
 	fn transaction_index(&self) -> Option<Index> {
 		self.transaction.transaction_index
 	}
+
+	fn status(&self) -> TransferStatus {
+		self.status.clone()
+	}
 }
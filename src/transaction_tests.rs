@@ -1,6 +1,16 @@
 use crate::{
-	transfer::Transfer,
-	transaction::TransactionAndTransferType,
+	erc20::{
+		Approval,
+		ERC20Call,
+	},
+	transfer::{
+		Transfer,
+		TransferType,
+	},
+	transaction::{
+		TransactionAndTransferType,
+		TransactionContractInvocation,
+	},
 	ERC20Error,
 };
 use std::{
@@ -31,6 +41,7 @@ fn parse_no_transfer_transaction() {
 		gas: Default::default(),
 		input: Bytes(hex::decode(serialized_str).unwrap()),
 		raw: None,
+		..Default::default()
 	};
 
 	let resp: Result<TransactionAndTransferType, ERC20Error> = transaction.try_into();
@@ -48,17 +59,151 @@ fn parse_erc20() {
 		block_hash: None,
 		block_number: None,
 		transaction_index: None,
-		from: H160::random(),
+		from: Some(H160::random()),
 		to: Some(H160::random()),
 		value: Default::default(),
 		gas_price: Default::default(),
 		gas: Default::default(),
 		input: Bytes(hex::decode(serialized_str).unwrap()),
 		raw: None,
+		..Default::default()
 	};
 
 	let resp: Result<TransactionAndTransferType, ERC20Error> = transaction.clone().try_into();
 	assert!(resp.is_ok());
 	let resp = resp.unwrap();
-	assert_eq!(transaction.from, resp.from());
+	assert_eq!(transaction.from, Some(resp.from()));
+}
+
+fn contract_invocation(input: Bytes) -> TransactionContractInvocation {
+	Transaction {
+		from: Some(H160::from_low_u64_be(1)),
+		to: Some(H160::from_low_u64_be(2)),
+		input,
+		..Default::default()
+	}.into()
+}
+
+#[test]
+fn parse_approval() {
+	let spender = H160::from_low_u64_be(3);
+	let invocation = contract_invocation(ERC20Call::Approve { spender, value: 10.into() }.encode());
+	assert_eq!(Ok(Approval {
+		owner: H160::from_low_u64_be(1),
+		spender,
+		value: 10.into(),
+	}), invocation.approval());
+
+	let transaction = invocation.transaction().clone();
+	let resp: Result<TransactionAndTransferType, ERC20Error> = transaction.try_into();
+	assert_eq!(ERC20Error::NoTransferTransaction, resp.err().unwrap());
+}
+
+#[test]
+fn parse_permit_approval() {
+	let (owner, spender) = (H160::from_low_u64_be(5), H160::from_low_u64_be(3));
+	let invocation = contract_invocation(ERC20Call::Permit {
+		owner,
+		spender,
+		value: 10.into(),
+		deadline: 1_700_000_000.into(),
+		v: 27,
+		r: H256::from_low_u64_be(1),
+		s: H256::from_low_u64_be(2),
+	}.encode());
+	// The owner is the permit signer, not the transaction sender.
+	assert_eq!(Ok(Approval { owner, spender, value: 10.into() }), invocation.approval());
+	assert_eq!(Some(1_700_000_000.into()), invocation.permit_approval().unwrap().deadline);
+
+	let invocation = contract_invocation(ERC20Call::Approve { spender, value: 10.into() }.encode());
+	assert_eq!(Err(ERC20Error::NoApprovalTransaction), invocation.permit_approval());
+}
+
+#[test]
+fn parse_no_approval() {
+	let invocation = contract_invocation(ERC20Call::Transfer { to: H160::random(), value: 10.into() }.encode());
+	assert_eq!(Err(ERC20Error::NoApprovalTransaction), invocation.approval());
+
+	let invocation = contract_invocation(Bytes(hex::decode("d0e30db0").unwrap()));
+	assert_eq!(Err(ERC20Error::NoApprovalTransaction), invocation.approval());
+	assert_eq!(Err(ERC20Error::UnexpectedType), invocation.erc20_call());
+
+	let mut transaction = contract_invocation(ERC20Call::Approve { spender: H160::random(), value: 1.into() }.encode())
+		.transaction()
+		.clone();
+	transaction.from = None;
+	let invocation: TransactionContractInvocation = transaction.into();
+	assert_eq!(Err(ERC20Error::UnknownSender), invocation.approval());
+}
+
+#[test]
+fn parse_read_calls() {
+	let (owner, spender) = (H160::random(), H160::random());
+	let invocation = contract_invocation(ERC20Call::BalanceOf { owner }.encode());
+	assert_eq!(Ok(ERC20Call::BalanceOf { owner }), invocation.erc20_call());
+
+	let invocation = contract_invocation(ERC20Call::Allowance { owner, spender }.encode());
+	assert_eq!(Ok(ERC20Call::Allowance { owner, spender }), invocation.erc20_call());
+}
+
+#[test]
+fn parse_truncated_erc20() {
+	// The value is missing, it fails on creation instead of on the `Transfer` accessors.
+	let serialized_str = "a9059cbb0000000000000000000000006748f50f686bfbca6fe8ad62b22228b87f31ff2b";
+	let transaction = Transaction {
+		from: Some(H160::random()),
+		to: Some(H160::random()),
+		input: Bytes(hex::decode(serialized_str).unwrap()),
+		..Default::default()
+	};
+
+	let resp: Result<TransactionAndTransferType, ERC20Error> = transaction.try_into();
+	assert_eq!(ERC20Error::UnexpectedEndOfData, resp.err().unwrap());
+}
+
+#[test]
+fn parse_transfer_with_unknown_sender() {
+	let transaction = Transaction {
+		from: None,
+		to: Some(H160::random()),
+		value: 10.into(),
+		..Default::default()
+	};
+	let resp: Result<TransactionAndTransferType, ERC20Error> = transaction.try_into();
+	assert_eq!(ERC20Error::UnknownSender, resp.err().unwrap());
+
+	// `transferFrom` carries the sender in its input.
+	let (from, to) = (H160::random(), H160::random());
+	let transaction = Transaction {
+		from: None,
+		to: Some(H160::random()),
+		input: ERC20Call::TransferFrom { from, to, value: 10.into() }.encode(),
+		..Default::default()
+	};
+	let resp: TransactionAndTransferType = transaction.try_into().unwrap();
+	assert_eq!(from, resp.from());
+	assert_eq!(to, resp.to());
+	assert_eq!(TransferType::ERC20, resp.transfer_type());
+}
+
+#[test]
+fn parse_mint_and_burn() {
+	let (contract, sender, holder) = (H160::from_low_u64_be(2), H160::from_low_u64_be(1), H160::from_low_u64_be(3));
+	let calls = vec![
+		(ERC20Call::Mint { to: holder, value: 10.into() }, H160::zero(), holder),
+		(ERC20Call::Burn { value: 10.into() }, sender, H160::zero()),
+		(ERC20Call::BurnFrom { from: holder, value: 10.into() }, holder, H160::zero()),
+	];
+	for (call, from, to) in calls {
+		let transaction = contract_invocation(call.encode()).transaction().clone();
+		let transfer: TransactionAndTransferType = transaction.try_into().unwrap();
+		assert_eq!(TransferType::ERC20, transfer.transfer_type());
+		assert_eq!(Ok((from, to, 10.into())), transfer.get_from_to_value());
+		assert_eq!(Some(contract), transfer.contract());
+	}
+
+	// Allowance changes are not transfers.
+	let call = ERC20Call::IncreaseAllowance { spender: holder, value: 10.into() };
+	let resp: Result<TransactionAndTransferType, ERC20Error> = contract_invocation(call.encode()).transaction().clone().try_into();
+	assert_eq!(ERC20Error::NoTransferTransaction, resp.err().unwrap());
 }
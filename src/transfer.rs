@@ -1,9 +1,17 @@
-///! Ethereum transfer abstraction.
+//! Ethereum transfer abstraction.
 
+use crate::{
+	amount::TokenAmount,
+	chain,
+	event::TransferLog,
+	registry::TokenRegistry,
+	transaction::TransactionAndTransferType,
+};
 use serde::{
 	Deserialize,
 	Serialize,
 };
+use std::cmp::Ordering;
 use web3::types::{
 	BlockId,
 	BlockNumber,
@@ -16,7 +24,7 @@ use web3::types::{
 };
 
 /// Type of the asset transfer.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TransferType {
 	/// Indicates an Ether transfer.
@@ -25,8 +33,24 @@ pub enum TransferType {
 	ERC20,
 }
 
+/// Execution status of the transaction carrying the transfer.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransferStatus {
+	/// The transaction was executed successfully.
+	Succeeded,
+	/// The transaction was reverted, so the transfer did not happen.
+	Reverted,
+	/// The receipt is not available, or it predates the status field.
+	Unknown,
+}
+
 /// Asset transfer abstraction.
 pub trait Transfer {
+	/// Returns the chain id of the network of the transfer, if known.
+	fn chain_id(&self) -> Option<u64> {
+		None
+	}
 	/// Returns the sender of the transfer.
 	fn from(&self) -> H160;
 	/// Returns the recipient of the transfer.
@@ -43,6 +67,19 @@ pub trait Transfer {
 	fn block_number(&self) -> Option<U64>;
 	/// Returns the transaction index for the transfer, if available.
 	fn transaction_index(&self) -> Option<Index>;
+	/// Returns the execution status of the transfer, if known.
+	fn status(&self) -> TransferStatus {
+		TransferStatus::Unknown
+	}
+	/// Returns the value of the transfer scaled with the decimals of the asset, `None` if the chain
+	/// id is not known, or if the token or its decimals are not in `registry`.
+	fn amount(&self, registry: &TokenRegistry) -> Option<TokenAmount> {
+		let chain_id = self.chain_id()?;
+		match self.contract() {
+			None => TokenAmount::new(self.value(), chain::NATIVE_DECIMALS).ok(),
+			Some(contract) => registry.amount(chain_id, &contract, self.value()),
+		}
+	}
 }
 
 impl dyn Transfer {
@@ -62,6 +99,11 @@ impl dyn Transfer {
 	/// Checks if it is an ERC20 transfer.
 	pub fn is_erc20(&self) -> bool { !self.is_ethereum() }
 
+	/// Checks if the transaction carrying the transfer was reverted.
+	pub fn is_reverted(&self) -> bool {
+		self.status() == TransferStatus::Reverted
+	}
+
 	/// Retrieves the transaction id.
 	#[allow(dead_code)]
 	fn transaction_id(&self) -> TransactionId {
@@ -76,3 +118,153 @@ impl dyn Transfer {
 		}
 	}
 }
+
+/// Plain transfer value, decoded once from a transaction or a log, so it is cheap to store, sort, and
+/// deduplicate.
+///
+/// It is ordered by its position in the chain: block number, transaction index, and log index.
+/// Transfers with an unknown position come first, as do the transaction input transfers, with no log
+/// index, among the logs of the same transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedTransfer {
+	/// The chain id, if known.
+	#[serde(default)]
+	pub chain_id: Option<u64>,
+	/// The kind of transfer.
+	pub kind: TransferType,
+	/// The sender.
+	pub from: H160,
+	/// The recipient.
+	pub to: H160,
+	/// The ERC20 contract address for ERC20 transfers.
+	pub contract: Option<H160>,
+	/// The value transferred.
+	pub value: U256,
+	/// The transaction hash.
+	pub tx_hash: H256,
+	/// The block hash, if available.
+	pub block_hash: Option<H256>,
+	/// The block number, if available.
+	pub block_number: Option<U64>,
+	/// The transaction index in the block, if available.
+	pub tx_index: Option<Index>,
+	/// The log index in the block, for transfers decoded from logs.
+	pub log_index: Option<U256>,
+	/// The execution status of the transaction.
+	pub status: TransferStatus,
+}
+
+impl DecodedTransfer {
+	fn from_transfer(transfer: &dyn Transfer, log_index: Option<U256>) -> Self {
+		Self {
+			chain_id: transfer.chain_id(),
+			kind: match transfer.contract() {
+				None => TransferType::Ethereum,
+				Some(_) => TransferType::ERC20,
+			},
+			from: transfer.from(),
+			to: transfer.to(),
+			contract: transfer.contract(),
+			value: transfer.value(),
+			tx_hash: transfer.tx_hash(),
+			block_hash: transfer.block_hash(),
+			block_number: transfer.block_number(),
+			tx_index: transfer.transaction_index(),
+			log_index,
+			status: transfer.status(),
+		}
+	}
+
+	/// Returns the position of the transfer in the chain.
+	pub fn position(&self) -> (Option<U64>, Option<Index>, Option<U256>) {
+		(self.block_number, self.tx_index, self.log_index)
+	}
+}
+
+impl From<&TransactionAndTransferType> for DecodedTransfer {
+	fn from(transfer: &TransactionAndTransferType) -> Self {
+		Self::from_transfer(transfer, None)
+	}
+}
+
+impl From<TransactionAndTransferType> for DecodedTransfer {
+	fn from(transfer: TransactionAndTransferType) -> Self {
+		(&transfer).into()
+	}
+}
+
+impl From<&TransferLog> for DecodedTransfer {
+	fn from(transfer: &TransferLog) -> Self {
+		Self::from_transfer(transfer, transfer.log_index())
+	}
+}
+
+impl From<TransferLog> for DecodedTransfer {
+	fn from(transfer: TransferLog) -> Self {
+		(&transfer).into()
+	}
+}
+
+impl Ord for DecodedTransfer {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.position().cmp(&other.position())
+			.then_with(|| self.tx_hash.cmp(&other.tx_hash))
+			.then_with(|| self.block_hash.cmp(&other.block_hash))
+			.then_with(|| self.kind.cmp(&other.kind))
+			.then_with(|| self.contract.cmp(&other.contract))
+			.then_with(|| self.from.cmp(&other.from))
+			.then_with(|| self.to.cmp(&other.to))
+			.then_with(|| self.value.cmp(&other.value))
+			.then_with(|| self.status.cmp(&other.status))
+			.then_with(|| self.chain_id.cmp(&other.chain_id))
+	}
+}
+
+impl PartialOrd for DecodedTransfer {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Transfer for DecodedTransfer {
+	fn chain_id(&self) -> Option<u64> {
+		self.chain_id
+	}
+
+	fn from(&self) -> H160 {
+		self.from
+	}
+
+	fn to(&self) -> H160 {
+		self.to
+	}
+
+	fn contract(&self) -> Option<H160> {
+		self.contract
+	}
+
+	fn value(&self) -> U256 {
+		self.value
+	}
+
+	fn tx_hash(&self) -> H256 {
+		self.tx_hash
+	}
+
+	fn block_hash(&self) -> Option<H256> {
+		self.block_hash
+	}
+
+	fn block_number(&self) -> Option<U64> {
+		self.block_number
+	}
+
+	fn transaction_index(&self) -> Option<Index> {
+		self.tx_index
+	}
+
+	fn status(&self) -> TransferStatus {
+		self.status.clone()
+	}
+}
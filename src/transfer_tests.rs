@@ -0,0 +1,164 @@
+use crate::{
+	amount::TokenAmount,
+	chain,
+	classifier::TransactionClassifier,
+	erc20::{
+		ContractAddress,
+		ERC20Call,
+	},
+	event::{
+		TransferLog,
+		TRANSFER_EVENT_TOPIC,
+	},
+	registry::{
+		TokenInfo,
+		TokenRegistry,
+	},
+	transaction::TransactionAndTransferType,
+	transfer::{
+		DecodedTransfer,
+		Transfer,
+		TransferStatus,
+		TransferType,
+	},
+};
+use std::{
+	collections::HashSet,
+	convert::TryInto,
+};
+use web3::types::{
+	Bytes,
+	Log,
+	Transaction,
+	H160,
+	H256,
+};
+
+fn transaction_transfer(block_number: u64, transaction_index: u64) -> TransactionAndTransferType {
+	Transaction {
+		hash: H256::from_low_u64_be(block_number * 100 + transaction_index),
+		block_number: Some(block_number.into()),
+		transaction_index: Some(transaction_index.into()),
+		from: Some(H160::from_low_u64_be(1)),
+		to: Some(H160::from_low_u64_be(2)),
+		input: ERC20Call::Transfer { to: H160::from_low_u64_be(3), value: 10.into() }.encode(),
+		..Default::default()
+	}.try_into().unwrap()
+}
+
+fn log_transfer(block_number: u64, transaction_index: u64, log_index: u64) -> TransferLog {
+	Log {
+		address: H160::from_low_u64_be(2),
+		topics: vec![
+			H256(TRANSFER_EVENT_TOPIC),
+			H256::from_low_u64_be(1),
+			H256::from_low_u64_be(3),
+		],
+		data: Bytes(H256::from_low_u64_be(10).0.to_vec()),
+		block_hash: None,
+		block_number: Some(block_number.into()),
+		transaction_hash: Some(H256::from_low_u64_be(block_number * 100 + transaction_index)),
+		transaction_index: Some(transaction_index.into()),
+		log_index: Some(log_index.into()),
+		transaction_log_index: None,
+		log_type: None,
+		removed: None,
+	}.try_into().unwrap()
+}
+
+#[test]
+fn from_transaction_and_log() {
+	let from_transaction: DecodedTransfer = transaction_transfer(5, 1).into();
+	assert_eq!(TransferType::ERC20, from_transaction.kind);
+	assert_eq!(H160::from_low_u64_be(1), from_transaction.from);
+	assert_eq!(H160::from_low_u64_be(3), from_transaction.to);
+	assert_eq!(Some(H160::from_low_u64_be(2)), from_transaction.contract);
+	assert_eq!(None, from_transaction.log_index);
+	assert_eq!(TransferStatus::Unknown, from_transaction.status());
+
+	let from_log: DecodedTransfer = log_transfer(5, 1, 7).into();
+	assert_eq!(Some(7.into()), from_log.log_index);
+	assert_eq!(TransferStatus::Succeeded, from_log.status);
+	assert_eq!(from_transaction.tx_hash, from_log.tx_hash());
+	assert_eq!(from_transaction.value(), from_log.value());
+}
+
+#[test]
+fn ordered_by_chain_position() {
+	let mut transfers: Vec<DecodedTransfer> = vec![
+		log_transfer(6, 0, 0).into(),
+		log_transfer(5, 1, 8).into(),
+		transaction_transfer(5, 1).into(),
+		log_transfer(5, 1, 7).into(),
+		transaction_transfer(5, 0).into(),
+	];
+	transfers.sort();
+	let positions: Vec<(u64, u64, Option<u64>)> = transfers.iter()
+		.map(|it| (
+			it.block_number.unwrap().as_u64(),
+			it.tx_index.unwrap().as_u64(),
+			it.log_index.map(|log_index| log_index.as_u64()),
+		))
+		.collect();
+	assert_eq!(vec![(5, 0, None), (5, 1, None), (5, 1, Some(7)), (5, 1, Some(8)), (6, 0, Some(0))], positions);
+}
+
+#[test]
+fn deduplicated_by_hash() {
+	let transfers: HashSet<DecodedTransfer> = vec![
+		log_transfer(5, 1, 7).into(),
+		log_transfer(5, 1, 7).into(),
+		transaction_transfer(5, 1).into(),
+	].into_iter().collect();
+	assert_eq!(2, transfers.len());
+}
+
+#[test]
+fn serde_round_trip() {
+	let transfer: DecodedTransfer = log_transfer(5, 1, 7).into();
+	let serialized = serde_json::to_string(&transfer).unwrap();
+	assert!(serialized.contains("\"logIndex\":\"0x7\""));
+	let deserialized: DecodedTransfer = serde_json::from_str(&serialized).unwrap();
+	assert_eq!(transfer, deserialized);
+}
+
+#[test]
+fn scaled_amounts() {
+	let registry = TokenRegistry::builtin()
+		.with_token(TokenInfo::new(chain::MAINNET, H160::from_low_u64_be(2), "UNKNOWN"))
+		.with_token(TokenInfo::new(chain::POLYGON, H160::from_low_u64_be(2), "TWO").with_metadata("Two", 2));
+	let usdc = |chain_id| -> TransactionAndTransferType {
+		let transaction = Transaction {
+			from: Some(H160::from_low_u64_be(1)),
+			to: ContractAddress::USDC.address(chain_id),
+			input: ERC20Call::Transfer { to: H160::from_low_u64_be(3), value: 1_500_000.into() }.encode(),
+			..Default::default()
+		};
+		TransactionClassifier::new().with_chain_id(chain_id).classify(transaction).try_into().unwrap()
+	};
+	assert_eq!(TokenAmount::new(1_500_000.into(), 6).ok(), usdc(chain::MAINNET).amount(&registry));
+	assert_eq!("1.500000", usdc(chain::POLYGON).amount(&registry).unwrap().to_string());
+	assert_eq!("0.000000000001500000", usdc(chain::BSC).amount(&registry).unwrap().to_string());
+
+	let ether = Transaction {
+		from: Some(H160::from_low_u64_be(1)),
+		to: Some(H160::from_low_u64_be(3)),
+		value: 1_000_000_000_000_000_000u64.into(),
+		..Default::default()
+	};
+	let transfer: TransactionAndTransferType = TransactionClassifier::new().with_chain_id(chain::MAINNET)
+		.classify(ether.clone()).try_into().unwrap();
+	assert_eq!("1.000000000000000000", transfer.amount(&registry).unwrap().to_string());
+	let transfer: TransactionAndTransferType = ether.try_into().unwrap();
+	assert_eq!(None, transfer.amount(&registry));
+
+	// Unknown decimals or chain are not guessed.
+	assert_eq!(None, transaction_transfer(5, 1).amount(&registry));
+	assert_eq!(None, log_transfer(5, 1, 7).amount(&registry));
+	assert_eq!(None, log_transfer(5, 1, 7).with_chain_id(chain::MAINNET).amount(&registry));
+	assert_eq!("0.10", log_transfer(5, 1, 7).with_chain_id(chain::POLYGON).amount(&registry).unwrap().to_string());
+	let decoded: DecodedTransfer = log_transfer(5, 1, 7).with_chain_id(chain::POLYGON).into();
+	assert_eq!(Some(chain::POLYGON), decoded.chain_id);
+	let decoded: DecodedTransfer = usdc(chain::ARBITRUM).into();
+	assert_eq!(TokenAmount::new(1_500_000.into(), 6).ok(), decoded.amount(&registry));
+}
@@ -1,6 +1,12 @@
-///! A set of useful methods and abstractions.
+//! A set of useful methods and abstractions.
 
-use crate::ERC20Error;
+use crate::{
+	abi::{
+		ParamType,
+		Token,
+	},
+	ERC20Error,
+};
 use web3::types::{
 	Bytes,
 	H160,
@@ -8,13 +14,16 @@ use web3::types::{
 	U256,
 };
 
-const WORD_SIZE_256_BITS: usize = 32;
+pub(crate) const WORD_SIZE_256_BITS: usize = 32;
 const WORD_SIZE_160_BITS: usize = 20;
 
-/// Converts `Bytes` and `Vec<u8>` to H160, H256, and U256.
+/// Converts `Bytes` and `Vec<u8>` to H160, H256, U256, and ABI encoded `Token`s.
 pub struct BytesToFixedNumber {
 	data: Vec<u8>,
 	index: usize,
+	// Bytes read so far. Offsets may jump back, so this is capped by `data.len()`
+	// to keep aliased dynamic parameters from decoding the same data over and over.
+	read: usize,
 }
 
 impl From<Vec<u8>> for BytesToFixedNumber {
@@ -23,6 +32,7 @@ impl From<Vec<u8>> for BytesToFixedNumber {
 		Self {
 			data,
 			index: 0,
+			read: 0,
 		}
 	}
 }
@@ -42,9 +52,10 @@ impl BytesToFixedNumber {
 	/// * `size` - The size requested for the next vector.
 	///
 	pub fn next_vec(&mut self, size: usize) -> Result<Vec<u8>, ERC20Error> {
-		if self.index + size > self.data.len() {
+		if size > self.data.len() - self.index {
 			return Err(ERC20Error::UnexpectedEndOfData);
 		}
+		self.consume(size)?;
 		let mut resp = Vec::new();
 		for i in 0..size {
 			resp.push(self.data[self.index + i]);
@@ -60,13 +71,22 @@ impl BytesToFixedNumber {
 	/// * `size` - The number of bytes to skip.
 	///
 	pub fn skip(&mut self, size: usize) -> Result<(), ERC20Error> {
-		if self.index + size > self.data.len() {
+		if size > self.data.len() - self.index {
 			return Err(ERC20Error::UnexpectedEndOfData);
 		}
+		self.consume(size)?;
 		self.index += size;
 		Ok(())
 	}
 
+	fn consume(&mut self, size: usize) -> Result<(), ERC20Error> {
+		self.read += size;
+		if self.read > self.data.len() {
+			return Err(ERC20Error::UnexpectedSize);
+		}
+		Ok(())
+	}
+
 	/// Returns the next H160.
 	pub fn next_h160(&mut self) -> Result<H160, ERC20Error> {
 		self.skip(WORD_SIZE_256_BITS - WORD_SIZE_160_BITS)?;
@@ -96,9 +116,149 @@ impl BytesToFixedNumber {
 		the_vec[..WORD_SIZE_256_BITS].clone_from_slice(&vec_resp[..WORD_SIZE_256_BITS]);
 		Ok(the_vec.into())
 	}
+
+	/// Returns the next U256 as an `usize`, as used for offsets and lengths.
+	pub fn next_usize(&mut self) -> Result<usize, ERC20Error> {
+		let value = self.next_u256()?;
+		if value > U256::from(usize::MAX) {
+			return Err(ERC20Error::UnexpectedSize);
+		}
+		Ok(value.as_usize())
+	}
+
+	/// Returns the next U256 as an `u8`, as used for `uint8` parameters.
+	pub fn next_u8(&mut self) -> Result<u8, ERC20Error> {
+		let value = self.next_u256()?;
+		if value > U256::from(u8::MAX) {
+			return Err(ERC20Error::UnexpectedType);
+		}
+		Ok(value.low_u32() as u8)
+	}
+
+	/// Returns the next bool, failing if the word is neither zero nor one.
+	pub fn next_bool(&mut self) -> Result<bool, ERC20Error> {
+		self.next_static_token(&ParamType::Bool)
+			.map(|token| token.into_bool().expect("A bool parameter is decoded as a bool"))
+	}
+
+	/// Returns the next tokens, decoding a sequence of ABI encoded parameters.
+	/// The offsets of the dynamic parameters are relative to the current position.
+	///
+	/// # Arguments
+	///
+	/// * `params` - The types of the parameters to be decoded.
+	///
+	pub fn next_tokens(&mut self, params: &[ParamType]) -> Result<Vec<Token>, ERC20Error> {
+		let base = self.index;
+		params.iter().map(|param| self.next_head_token(param, base)).collect()
+	}
+
+	/// Returns the next token, decoding a single ABI encoded parameter.
+	///
+	/// # Arguments
+	///
+	/// * `param` - The type of the parameter to be decoded.
+	///
+	pub fn next_token(&mut self, param: &ParamType) -> Result<Token, ERC20Error> {
+		let base = self.index;
+		self.next_head_token(param, base)
+	}
+
+	fn next_head_token(&mut self, param: &ParamType, base: usize) -> Result<Token, ERC20Error> {
+		if !param.is_dynamic() {
+			return self.next_static_token(param);
+		}
+		let offset = self.next_usize()?;
+		let head_end = self.index;
+		self.index = base.checked_add(offset).ok_or(ERC20Error::UnexpectedSize)?;
+		if self.index > self.data.len() {
+			return Err(ERC20Error::UnexpectedEndOfData);
+		}
+		let token = self.next_dynamic_token(param);
+		self.index = head_end;
+		token
+	}
+
+	fn next_static_token(&mut self, param: &ParamType) -> Result<Token, ERC20Error> {
+		match param {
+			ParamType::Address => Ok(Token::Address(self.next_h160()?)),
+			ParamType::Int(_) => Ok(Token::Int(self.next_u256()?)),
+			ParamType::Uint(_) => Ok(Token::Uint(self.next_u256()?)),
+			ParamType::Bool => {
+				let value = self.next_u256()?;
+				if value > U256::one() {
+					return Err(ERC20Error::UnexpectedType);
+				}
+				Ok(Token::Bool(value == U256::one()))
+			}
+			ParamType::FixedBytes(size) => {
+				if *size == 0 || *size > WORD_SIZE_256_BITS {
+					return Err(ERC20Error::UnexpectedType);
+				}
+				let mut word = self.next_vec(WORD_SIZE_256_BITS)?;
+				word.truncate(*size);
+				Ok(Token::FixedBytes(word))
+			}
+			ParamType::FixedArray(param, size) => {
+				let mut tokens = Vec::new();
+				for _ in 0..*size {
+					tokens.push(self.next_static_token(param)?);
+				}
+				Ok(Token::FixedArray(tokens))
+			}
+			ParamType::Tuple(params) => {
+				let tokens = params.iter()
+					.map(|param| self.next_static_token(param))
+					.collect::<Result<Vec<Token>, ERC20Error>>()?;
+				Ok(Token::Tuple(tokens))
+			}
+			ParamType::Bytes | ParamType::String | ParamType::Array(_) => Err(ERC20Error::UnexpectedType),
+		}
+	}
+
+	fn next_dynamic_token(&mut self, param: &ParamType) -> Result<Token, ERC20Error> {
+		match param {
+			ParamType::Bytes => {
+				let size = self.next_usize()?;
+				Ok(Token::Bytes(self.next_vec(size)?))
+			}
+			ParamType::String => {
+				let size = self.next_usize()?;
+				String::from_utf8(self.next_vec(size)?)
+					.map(Token::String)
+					.map_err(|_| ERC20Error::UnexpectedType)
+			}
+			ParamType::Array(param) => {
+				let size = self.next_usize()?;
+				Ok(Token::Array(self.next_head_tokens(param, size)?))
+			}
+			ParamType::FixedArray(param, size) => Ok(Token::FixedArray(self.next_head_tokens(param, *size)?)),
+			ParamType::Tuple(params) => Ok(Token::Tuple(self.next_tokens(params)?)),
+			_ => self.next_static_token(param),
+		}
+	}
+
+	fn next_head_tokens(&mut self, param: &ParamType, size: usize) -> Result<Vec<Token>, ERC20Error> {
+		let base = self.index;
+		let remaining = self.data.len() - base;
+		// Elements with no head, e.g. empty tuples, would let any length pass the bounds check.
+		if param.head_size() == 0 && size > 0 {
+			return Err(ERC20Error::UnexpectedType);
+		}
+		match size.checked_mul(param.head_size()) {
+			Some(heads_size) if heads_size <= remaining => {}
+			_ => return Err(ERC20Error::UnexpectedEndOfData),
+		}
+		let mut tokens = Vec::new();
+		for _ in 0..size {
+			tokens.push(self.next_head_token(param, base)?);
+		}
+		Ok(tokens)
+	}
 }
 
-/// Converts H160, H256, and U256 into `Vec<u8>` which can be used to create a `Bytes`.
+/// Converts H160, H256, U256, and ABI `Token`s into `Vec<u8>` which can be used to create a `Bytes`.
+#[derive(Default)]
 pub struct FixedNumberToBytes {
 	data: Vec<u8>,
 }
@@ -166,12 +326,107 @@ impl FixedNumberToBytes {
 			self.data.push(value.byte(i));
 		}
 	}
-}
 
-impl Default for FixedNumberToBytes {
-	fn default() -> Self {
-		Self {
-			data: Vec::new(),
+	/// Pushes an `usize` as an U256, as used for offsets and lengths.
+	///
+	/// # Arguments
+	///
+	/// * `value` - `usize` to be pushed.
+	///
+	pub fn push_usize(&mut self, value: usize) {
+		self.push_u256(&value.into());
+	}
+
+	/// Pushes a bool to the tail of the current byte array.
+	///
+	/// # Arguments
+	///
+	/// * `value` - bool to be pushed.
+	///
+	pub fn push_bool(&mut self, value: bool) {
+		self.push_u256(&(value as u8).into());
+	}
+
+	/// Pushes a vector of bytes padded to the right to a multiple of 32 bytes.
+	///
+	/// # Arguments
+	///
+	/// * `vec` - Vector with the bytes to be added.
+	///
+	pub fn push_vec_padded(&mut self, vec: &[u8]) {
+		self.push_vec(vec);
+		let padding = (WORD_SIZE_256_BITS - vec.len() % WORD_SIZE_256_BITS) % WORD_SIZE_256_BITS;
+		self.data.resize(self.data.len() + padding, 0);
+	}
+
+	/// Pushes a sequence of ABI encoded parameters, with the heads followed by the tails.
+	/// Fails with `UnexpectedSize` if a fixed bytes value does not have 1 to 32 bytes.
+	///
+	/// # Arguments
+	///
+	/// * `tokens` - The parameters to be encoded.
+	///
+	pub fn push_tokens(&mut self, tokens: &[Token]) -> Result<(), ERC20Error> {
+		let heads_size: usize = tokens.iter().map(|it| it.head_size()).sum();
+		let mut tail = FixedNumberToBytes::default();
+		for token in tokens {
+			if token.is_dynamic() {
+				self.push_usize(heads_size + tail.data.len());
+				tail.push_dynamic_token(token)?;
+			} else {
+				self.push_static_token(token)?;
+			}
+		}
+		self.push_vec(&tail.data);
+		Ok(())
+	}
+
+	/// Pushes a single ABI encoded parameter.
+	///
+	/// # Arguments
+	///
+	/// * `token` - The parameter to be encoded.
+	///
+	pub fn push_token(&mut self, token: &Token) -> Result<(), ERC20Error> {
+		self.push_tokens(std::slice::from_ref(token))
+	}
+
+	fn push_static_token(&mut self, token: &Token) -> Result<(), ERC20Error> {
+		match token {
+			Token::Address(value) => self.push_h160(value),
+			Token::Int(value) | Token::Uint(value) => self.push_u256(value),
+			Token::Bool(value) => self.push_bool(*value),
+			// A longer value would take more than its word in the head.
+			Token::FixedBytes(value) if value.is_empty() || value.len() > WORD_SIZE_256_BITS => {
+				return Err(ERC20Error::UnexpectedSize);
+			}
+			Token::FixedBytes(value) => self.push_vec_padded(value),
+			Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+				return tokens.iter().try_for_each(|it| self.push_static_token(it));
+			}
+			Token::Bytes(_) | Token::String(_) | Token::Array(_) => return self.push_dynamic_token(token),
+		}
+		Ok(())
+	}
+
+	fn push_dynamic_token(&mut self, token: &Token) -> Result<(), ERC20Error> {
+		match token {
+			Token::Bytes(value) => {
+				self.push_usize(value.len());
+				self.push_vec_padded(value);
+				Ok(())
+			}
+			Token::String(value) => {
+				self.push_usize(value.len());
+				self.push_vec_padded(value.as_bytes());
+				Ok(())
+			}
+			Token::Array(tokens) => {
+				self.push_usize(tokens.len());
+				self.push_tokens(tokens)
+			}
+			Token::FixedArray(tokens) | Token::Tuple(tokens) => self.push_tokens(tokens),
+			_ => self.push_static_token(token),
 		}
 	}
 }
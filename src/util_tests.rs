@@ -1,6 +1,13 @@
-use crate::util::{
-	BytesToFixedNumber,
-	FixedNumberToBytes,
+use crate::{
+	abi::{
+		ParamType,
+		Token,
+	},
+	util::{
+		BytesToFixedNumber,
+		FixedNumberToBytes,
+	},
+	ERC20Error,
 };
 use web3::types::{
 	H160,
@@ -48,3 +55,308 @@ fn fixed_number_to_bytes() {
 
 	assert_eq!(bytes_vec, encoded_vec);
 }
+
+// f(uint256,uint32[],bytes10,bytes) with (0x123, [0x456, 0x789], "1234567890", "Hello, world!")
+const STATIC_AND_DYNAMIC_PARAMS: [&str; 9] = [
+	"0000000000000000000000000000000000000000000000000000000000000123",
+	"0000000000000000000000000000000000000000000000000000000000000080",
+	"3132333435363738393000000000000000000000000000000000000000000000",
+	"00000000000000000000000000000000000000000000000000000000000000e0",
+	"0000000000000000000000000000000000000000000000000000000000000002",
+	"0000000000000000000000000000000000000000000000000000000000000456",
+	"0000000000000000000000000000000000000000000000000000000000000789",
+	"000000000000000000000000000000000000000000000000000000000000000d",
+	"48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+];
+
+fn static_and_dynamic_params() -> (Vec<ParamType>, Vec<Token>) {
+	(vec![
+		ParamType::Uint(256),
+		ParamType::Array(Box::new(ParamType::Uint(32))),
+		ParamType::FixedBytes(10),
+		ParamType::Bytes,
+	], vec![
+		Token::Uint(0x123.into()),
+		Token::Array(vec![Token::Uint(0x456.into()), Token::Uint(0x789.into())]),
+		Token::FixedBytes(b"1234567890".to_vec()),
+		Token::Bytes(b"Hello, world!".to_vec()),
+	])
+}
+
+// g(uint256[][],string[]) with ([[1, 2], [3]], ["one", "two", "three"])
+const NESTED_DYNAMIC_ARRAYS: [&str; 20] = [
+	"0000000000000000000000000000000000000000000000000000000000000040",
+	"0000000000000000000000000000000000000000000000000000000000000140",
+	"0000000000000000000000000000000000000000000000000000000000000002",
+	"0000000000000000000000000000000000000000000000000000000000000040",
+	"00000000000000000000000000000000000000000000000000000000000000a0",
+	"0000000000000000000000000000000000000000000000000000000000000002",
+	"0000000000000000000000000000000000000000000000000000000000000001",
+	"0000000000000000000000000000000000000000000000000000000000000002",
+	"0000000000000000000000000000000000000000000000000000000000000001",
+	"0000000000000000000000000000000000000000000000000000000000000003",
+	"0000000000000000000000000000000000000000000000000000000000000003",
+	"0000000000000000000000000000000000000000000000000000000000000060",
+	"00000000000000000000000000000000000000000000000000000000000000a0",
+	"00000000000000000000000000000000000000000000000000000000000000e0",
+	"0000000000000000000000000000000000000000000000000000000000000003",
+	"6f6e650000000000000000000000000000000000000000000000000000000000",
+	"0000000000000000000000000000000000000000000000000000000000000003",
+	"74776f0000000000000000000000000000000000000000000000000000000000",
+	"0000000000000000000000000000000000000000000000000000000000000005",
+	"7468726565000000000000000000000000000000000000000000000000000000",
+];
+
+fn nested_dynamic_arrays() -> (Vec<ParamType>, Vec<Token>) {
+	(vec![
+		ParamType::Array(Box::new(ParamType::Array(Box::new(ParamType::Uint(256))))),
+		ParamType::Array(Box::new(ParamType::String)),
+	], vec![
+		Token::Array(vec![
+			Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+			Token::Array(vec![Token::Uint(3.into())]),
+		]),
+		Token::Array(vec![
+			Token::String("one".to_string()),
+			Token::String("two".to_string()),
+			Token::String("three".to_string()),
+		]),
+	])
+}
+
+// ((address,uint256[]),bool[2]) with ((0x01, [2]), [true, false])
+const TUPLES_AND_FIXED_ARRAYS: [&str; 7] = [
+	"0000000000000000000000000000000000000000000000000000000000000060",
+	"0000000000000000000000000000000000000000000000000000000000000001",
+	"0000000000000000000000000000000000000000000000000000000000000000",
+	"0000000000000000000000000000000000000000000000000000000000000001",
+	"0000000000000000000000000000000000000000000000000000000000000040",
+	"0000000000000000000000000000000000000000000000000000000000000001",
+	"0000000000000000000000000000000000000000000000000000000000000002",
+];
+
+fn tuples_and_fixed_arrays() -> (Vec<ParamType>, Vec<Token>) {
+	(vec![
+		ParamType::Tuple(vec![ParamType::Address, ParamType::Array(Box::new(ParamType::Uint(256)))]),
+		ParamType::FixedArray(Box::new(ParamType::Bool), 2),
+	], vec![
+		Token::Tuple(vec![
+			Token::Address(H160::from_low_u64_be(1)),
+			Token::Array(vec![Token::Uint(2.into())]),
+		]),
+		Token::FixedArray(vec![Token::Bool(true), Token::Bool(false)]),
+	])
+}
+
+// (int8,int256[]) with (-2, [-1, 3])
+const SIGNED_INTEGERS: [&str; 5] = [
+	"fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe",
+	"0000000000000000000000000000000000000000000000000000000000000040",
+	"0000000000000000000000000000000000000000000000000000000000000002",
+	"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+	"0000000000000000000000000000000000000000000000000000000000000003",
+];
+
+fn signed_integers() -> (Vec<ParamType>, Vec<Token>) {
+	(vec![
+		ParamType::Int(8),
+		ParamType::Array(Box::new(ParamType::Int(256))),
+	], vec![
+		Token::int(-2),
+		Token::Array(vec![Token::int(-1), Token::int(3)]),
+	])
+}
+
+fn words_decoder(words: &[&str]) -> BytesToFixedNumber {
+	hex::decode(words.concat()).unwrap().into()
+}
+
+fn encoded_words(tokens: &[Token]) -> String {
+	let mut encoder: FixedNumberToBytes = Default::default();
+	encoder.push_tokens(tokens).unwrap();
+	let encoded_vec: Vec<u8> = encoder.into();
+	hex::encode(encoded_vec)
+}
+
+#[test]
+fn decode_static_and_dynamic_params() {
+	let (params, tokens) = static_and_dynamic_params();
+	let mut decoder = words_decoder(&["8be65246", &STATIC_AND_DYNAMIC_PARAMS.concat()]);
+	assert_eq!(hex::decode("8be65246").unwrap(), decoder.next_vec(4).unwrap());
+	assert_eq!(Ok(tokens), decoder.next_tokens(&params));
+}
+
+#[test]
+fn decode_nested_dynamic_arrays() {
+	let (params, tokens) = nested_dynamic_arrays();
+	let mut decoder = words_decoder(&NESTED_DYNAMIC_ARRAYS);
+	assert_eq!(Ok(tokens), decoder.next_tokens(&params));
+}
+
+#[test]
+fn decode_tuples_and_fixed_arrays() {
+	let (params, tokens) = tuples_and_fixed_arrays();
+	let mut decoder = words_decoder(&TUPLES_AND_FIXED_ARRAYS);
+	assert_eq!(Ok(tokens), decoder.next_tokens(&params));
+}
+
+#[test]
+fn decode_signed_integers() {
+	let (params, tokens) = signed_integers();
+	let mut decoder = words_decoder(&SIGNED_INTEGERS);
+	assert_eq!(Ok(tokens), decoder.next_tokens(&params));
+}
+
+#[test]
+fn decode_invalid_data() {
+	let mut decoder = words_decoder(&["0000000000000000000000000000000000000000000000000000000000000002"]);
+	assert_eq!(Err(ERC20Error::UnexpectedType), decoder.next_token(&ParamType::Bool));
+
+	// Offset pointing past the end of the data.
+	let mut decoder = words_decoder(&["0000000000000000000000000000000000000000000000000000000000000040"]);
+	assert_eq!(Err(ERC20Error::UnexpectedEndOfData), decoder.next_token(&ParamType::Bytes));
+
+	// Array length larger than the data available.
+	let mut decoder = words_decoder(&[
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"00000000000000000000000000000000000000000000000000000000ffffffff",
+	]);
+	assert_eq!(
+		Err(ERC20Error::UnexpectedEndOfData),
+		decoder.next_token(&ParamType::Array(Box::new(ParamType::Address))),
+	);
+
+	let mut decoder = words_decoder(&["ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"]);
+	assert_eq!(Err(ERC20Error::UnexpectedSize), decoder.next_token(&ParamType::String));
+}
+
+#[test]
+fn decode_zero_sized_elements() {
+	// A huge length of elements with no head must not be trusted.
+	let huge_array = [
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"000000000000000000000000000000000000000000000000ffffffffffffffff",
+	];
+	let zero_sized = vec![
+		ParamType::Array(Box::new(ParamType::Tuple(Vec::new()))),
+		ParamType::Array(Box::new(ParamType::FixedArray(Box::new(ParamType::Uint(256)), 0))),
+	];
+	for param in zero_sized {
+		let mut decoder = words_decoder(&huge_array);
+		assert_eq!(Err(ERC20Error::UnexpectedType), decoder.next_token(&param));
+	}
+
+	// An empty array of them is still fine.
+	let mut decoder = words_decoder(&[
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"0000000000000000000000000000000000000000000000000000000000000000",
+	]);
+	assert_eq!(
+		Ok(Token::Array(Vec::new())),
+		decoder.next_token(&ParamType::Array(Box::new(ParamType::Tuple(Vec::new())))),
+	);
+}
+
+#[test]
+fn decode_aliased_offsets() {
+	let nested = ParamType::Array(Box::new(ParamType::Array(Box::new(ParamType::Uint(256)))));
+
+	// Both elements of the outer array point to the same inner array.
+	let mut decoder = words_decoder(&[
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"0000000000000000000000000000000000000000000000000000000000000002",
+		"0000000000000000000000000000000000000000000000000000000000000040",
+		"0000000000000000000000000000000000000000000000000000000000000040",
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000000000000000000000000000000000000000000007",
+	]);
+	assert_eq!(Err(ERC20Error::UnexpectedSize), decoder.next_token(&nested));
+
+	// The same elements with their own inner arrays are fine.
+	let mut decoder = words_decoder(&[
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"0000000000000000000000000000000000000000000000000000000000000002",
+		"0000000000000000000000000000000000000000000000000000000000000040",
+		"0000000000000000000000000000000000000000000000000000000000000080",
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000000000000000000000000000000000000000000007",
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000000000000000000000000000000000000000000007",
+	]);
+	let inner = Token::Array(vec![Token::Uint(7.into())]);
+	assert_eq!(Ok(Token::Array(vec![inner.clone(), inner])), decoder.next_token(&nested));
+}
+
+#[test]
+fn decode_invalid_fixed_bytes() {
+	let word = ["0100000000000000000000000000000000000000000000000000000000000000"];
+	for size in [0, 33].iter() {
+		let mut decoder = words_decoder(&word);
+		assert_eq!(Err(ERC20Error::UnexpectedType), decoder.next_token(&ParamType::FixedBytes(*size)));
+	}
+	let mut decoder = words_decoder(&word);
+	assert_eq!(Ok(Token::FixedBytes(vec![1])), decoder.next_token(&ParamType::FixedBytes(1)));
+}
+
+#[test]
+fn encode_static_and_dynamic_params() {
+	let (_, tokens) = static_and_dynamic_params();
+	assert_eq!(STATIC_AND_DYNAMIC_PARAMS.concat(), encoded_words(&tokens));
+}
+
+#[test]
+fn encode_nested_dynamic_arrays() {
+	let (_, tokens) = nested_dynamic_arrays();
+	assert_eq!(NESTED_DYNAMIC_ARRAYS.concat(), encoded_words(&tokens));
+}
+
+#[test]
+fn encode_tuples_and_fixed_arrays() {
+	let (_, tokens) = tuples_and_fixed_arrays();
+	assert_eq!(TUPLES_AND_FIXED_ARRAYS.concat(), encoded_words(&tokens));
+}
+
+#[test]
+fn encode_signed_integers() {
+	let (_, tokens) = signed_integers();
+	assert_eq!(SIGNED_INTEGERS.concat(), encoded_words(&tokens));
+}
+
+#[test]
+fn encode_invalid_fixed_bytes() {
+	let mut encoder: FixedNumberToBytes = Default::default();
+	assert_eq!(Err(ERC20Error::UnexpectedSize), encoder.push_token(&Token::FixedBytes(Vec::new())));
+	assert_eq!(Err(ERC20Error::UnexpectedSize), encoder.push_token(&Token::FixedBytes(vec![1; 33])));
+	let nested = Token::Array(vec![Token::Tuple(vec![Token::FixedBytes(vec![1; 33])])]);
+	assert_eq!(Err(ERC20Error::UnexpectedSize), encoder.push_token(&nested));
+	assert_eq!(Ok(()), encoder.push_token(&Token::FixedBytes(vec![1; 32])));
+}
+
+#[test]
+fn encode_single_dynamic_token() {
+	let encoded = encoded_words(&[Token::String("Hello".to_string())]);
+	assert_eq!([
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"0000000000000000000000000000000000000000000000000000000000000005",
+		"48656c6c6f000000000000000000000000000000000000000000000000000000",
+	].concat(), encoded);
+
+	let mut encoder: FixedNumberToBytes = Default::default();
+	encoder.push_token(&Token::String("Hello".to_string())).unwrap();
+	let encoded_vec: Vec<u8> = encoder.into();
+	assert_eq!(encoded, hex::encode(encoded_vec));
+}
+
+#[test]
+fn decode_small_values() {
+	let mut encoder: FixedNumberToBytes = Default::default();
+	encoder.push_usize(27);
+	encoder.push_bool(true);
+	encoder.push_usize(256);
+	encoder.push_usize(2);
+	let mut decoder: BytesToFixedNumber = Vec::from(encoder).into();
+	assert_eq!(Ok(27), decoder.next_u8());
+	assert_eq!(Ok(true), decoder.next_bool());
+	assert_eq!(Err(ERC20Error::UnexpectedType), decoder.next_u8());
+	assert_eq!(Err(ERC20Error::UnexpectedType), decoder.next_bool());
+}